@@ -0,0 +1,411 @@
+//! Beam propagation through grids of mirrors, splitters and other deflecting cells.
+//!
+//! Each cell type maps an incoming [`Direction`] to the directions leaving it. Beam states are
+//! `(cell, direction)` pairs. A state whose cell sends the beam in more than one direction is a
+//! junction, and the path between two junctions is a straight forward walk. [`BeamGraph`] walks
+//! every such segment once, then memoises the cells reachable from each strongly connected group
+//! of junctions, so that tracing many entry points only walks up to their first junction.
+use bit_set::BitSet;
+
+use crate::custom_grid::{CustomGrid, Direction};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Right,
+    Direction::Left,
+];
+
+/// Cells lit by a beam, see [`BeamGraph::trace`].
+#[derive(Clone, Debug)]
+pub struct Energized {
+    cells: BitSet,
+    cols: usize,
+    loops: bool,
+}
+
+impl Energized {
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        self.cells.contains(row * self.cols + col)
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells
+            .iter()
+            .map(|cell| (cell / self.cols, cell % self.cols))
+    }
+
+    /// Whether some part of the beam goes round in circles instead of leaving the grid or being
+    /// absorbed.
+    pub fn loops(&self) -> bool {
+        self.loops
+    }
+}
+
+/// The end of a straight walk through non junction states.
+enum WalkEnd {
+    Junction(usize),
+    Exit,
+    Loop,
+}
+
+/// Segment graph of the beams of a grid, see the module documentation.
+#[derive(Clone, Debug)]
+pub struct BeamGraph {
+    rows: usize,
+    cols: usize,
+    /// For each cell and incoming direction, a bitmask of outgoing directions.
+    outgoing: Vec<[u8; 4]>,
+    /// Junction index of each state, if it is one.
+    junction_of: Vec<Option<usize>>,
+    /// Strongly connected component of each junction.
+    component_of: Vec<usize>,
+    /// Cells reachable from each component, and whether it can loop.
+    reachable: Vec<(BitSet, bool)>,
+}
+
+impl BeamGraph {
+    /// Builds the graph of `grid`, `deflect` giving the directions a beam leaves a cell in when
+    /// entering it going `direction`. An empty result absorbs the beam.
+    pub fn new<T: Eq + std::hash::Hash>(
+        grid: &CustomGrid<T>,
+        deflect: impl Fn(&T, Direction) -> Vec<Direction>,
+    ) -> Self {
+        let (rows, cols) = (grid.rows(), grid.cols());
+        let outgoing: Vec<[u8; 4]> = grid
+            .iter()
+            .map(|cell| {
+                DIRECTIONS.map(|direction| {
+                    deflect(cell, direction)
+                        .into_iter()
+                        .fold(0, |mask, out| mask | 1 << out as u8)
+                })
+            })
+            .collect();
+
+        let mut graph = Self {
+            rows,
+            cols,
+            outgoing,
+            junction_of: vec![None; rows * cols * 4],
+            component_of: vec![],
+            reachable: vec![],
+        };
+
+        let junctions: Vec<usize> = (0..rows * cols * 4)
+            .filter(|&state| graph.outgoing_mask(state).count_ones() > 1)
+            .collect();
+        for (junction, &state) in junctions.iter().enumerate() {
+            graph.junction_of[state] = Some(junction);
+        }
+
+        // walk the segments leaving every junction.
+        let mut walked = vec![usize::MAX; rows * cols * 4];
+        let mut edges = vec![vec![]; junctions.len()];
+        let mut own = vec![(BitSet::with_capacity(rows * cols), false); junctions.len()];
+        let mut walk_id = 0;
+        for (junction, &state) in junctions.iter().enumerate() {
+            own[junction].0.insert(state / 4);
+            for next in graph.next_states(state) {
+                let (cells, loops) = &mut own[junction];
+                walk_id += 1;
+                match graph.walk(next, cells, &mut walked, walk_id) {
+                    WalkEnd::Junction(target) => edges[junction].push(target),
+                    WalkEnd::Exit => {}
+                    WalkEnd::Loop => *loops = true,
+                }
+            }
+        }
+
+        graph.compute_components(&edges, own);
+        graph
+    }
+
+    fn outgoing_mask(&self, state: usize) -> u8 {
+        self.outgoing[state / 4][state % 4]
+    }
+
+    fn state(&self, row: usize, col: usize, direction: Direction) -> usize {
+        (row * self.cols + col) * 4 + direction as usize
+    }
+
+    /// States reached one step after leaving the cell of `state`.
+    fn next_states(&self, state: usize) -> impl Iterator<Item = usize> + '_ {
+        let mask = self.outgoing_mask(state);
+        let (row, col) = (state / 4 / self.cols, state / 4 % self.cols);
+
+        DIRECTIONS
+            .into_iter()
+            .filter(move |&direction| mask & 1 << direction as u8 != 0)
+            .filter_map(move |direction| {
+                let (row, col) = match direction {
+                    Direction::Up => (row.checked_sub(1)?, col),
+                    Direction::Down => (row + 1, col),
+                    Direction::Right => (row, col + 1),
+                    Direction::Left => (row, col.checked_sub(1)?),
+                };
+                (row < self.rows && col < self.cols).then(|| self.state(row, col, direction))
+            })
+    }
+
+    /// Follows the beam from `state` until it reaches a junction, marking the cells it lights.
+    /// `walked` stamps states with `walk_id` to detect loops without junctions.
+    fn walk(
+        &self,
+        mut state: usize,
+        cells: &mut BitSet,
+        walked: &mut [usize],
+        walk_id: usize,
+    ) -> WalkEnd {
+        loop {
+            if let Some(junction) = self.junction_of[state] {
+                return WalkEnd::Junction(junction);
+            }
+            if walked[state] == walk_id {
+                return WalkEnd::Loop;
+            }
+            walked[state] = walk_id;
+            cells.insert(state / 4);
+
+            match self.next_states(state).next() {
+                Some(next) => state = next,
+                None => return WalkEnd::Exit,
+            }
+        }
+    }
+
+    /// Tarjan's algorithm over the junctions. Components are completed successors first, so
+    /// the reachable cells of a component are built from already known ones.
+    fn compute_components(&mut self, edges: &[Vec<usize>], own: Vec<(BitSet, bool)>) {
+        let count = edges.len();
+        let mut index = vec![usize::MAX; count];
+        let mut low = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = vec![];
+        let mut next_index = 0;
+        self.component_of = vec![usize::MAX; count];
+
+        for root in 0..count {
+            if index[root] != usize::MAX {
+                continue;
+            }
+
+            let mut calls = vec![(root, 0)];
+            index[root] = next_index;
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((node, edge)) = calls.last_mut() {
+                let node = *node;
+                if let Some(&target) = edges[node].get(*edge) {
+                    *edge += 1;
+                    if index[target] == usize::MAX {
+                        index[target] = next_index;
+                        low[target] = next_index;
+                        next_index += 1;
+                        stack.push(target);
+                        on_stack[target] = true;
+                        calls.push((target, 0));
+                    } else if on_stack[target] {
+                        low[node] = low[node].min(index[target]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[node]);
+                }
+
+                if low[node] == index[node] {
+                    let component = self.reachable.len();
+                    let mut members = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        self.component_of[member] = component;
+                        members.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+
+                    let mut cells = BitSet::with_capacity(self.rows * self.cols);
+                    let mut loops = members.len() > 1;
+                    for &member in &members {
+                        cells.union_with(&own[member].0);
+                        loops |= own[member].1;
+                        for &target in &edges[member] {
+                            if self.component_of[target] == component {
+                                loops = true;
+                            } else {
+                                let (target_cells, target_loops) =
+                                    &self.reachable[self.component_of[target]];
+                                cells.union_with(target_cells);
+                                loops |= target_loops;
+                            }
+                        }
+                    }
+                    self.reachable.push((cells, loops));
+                }
+            }
+        }
+    }
+
+    /// Cells lit by a beam entering the cell at `(row, col)` going `direction`.
+    pub fn trace(&self, row: usize, col: usize, direction: Direction) -> Energized {
+        let mut cells = BitSet::with_capacity(self.rows * self.cols);
+        let mut walked = vec![usize::MAX; self.rows * self.cols * 4];
+
+        let loops = match self.walk(self.state(row, col, direction), &mut cells, &mut walked, 0) {
+            WalkEnd::Junction(junction) => {
+                let (reachable, loops) = &self.reachable[self.component_of[junction]];
+                cells.union_with(reachable);
+                *loops
+            }
+            WalkEnd::Exit => false,
+            WalkEnd::Loop => true,
+        };
+
+        Energized {
+            cells,
+            cols: self.cols,
+            loops,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_grid::input_to_grid;
+    use crate::custom_grid::Direction::{Down, Left, Right, Up};
+
+    fn mirrors(cell: &char, direction: Direction) -> Vec<Direction> {
+        match (cell, direction) {
+            ('/', Right) => vec![Up],
+            ('/', Up) => vec![Right],
+            ('/', Left) => vec![Down],
+            ('/', Down) => vec![Left],
+            ('\\', Right) => vec![Down],
+            ('\\', Down) => vec![Right],
+            ('\\', Left) => vec![Up],
+            ('\\', Up) => vec![Left],
+            ('|', Left | Right) => vec![Up, Down],
+            ('-', Up | Down) => vec![Left, Right],
+            ('#', _) => vec![],
+            _ => vec![direction],
+        }
+    }
+
+    fn graph(input: &str) -> BeamGraph {
+        let grid: CustomGrid<char> = input_to_grid(input).unwrap();
+        BeamGraph::new(&grid, mirrors)
+    }
+
+    #[test]
+    fn test_straight_beam() {
+        let graph = graph("...\n...\n");
+        let energized = graph.trace(1, 0, Right);
+        assert_eq!(
+            energized.cells().collect::<Vec<_>>(),
+            vec![(1, 0), (1, 1), (1, 2)]
+        );
+        assert!(!energized.loops());
+        assert!(!energized.contains(0, 0));
+    }
+
+    #[test]
+    fn test_absorbed_beam() {
+        let energized = graph("..#.\n").trace(0, 0, Right);
+        assert_eq!(energized.len(), 3);
+        assert!(!energized.loops());
+    }
+
+    #[test]
+    fn test_mirror_loop() {
+        // the splitter sends the beam up into a square of mirrors, which brings it back.
+        let graph = graph(
+            "\
+            /..\\\n\
+            ....\n\
+            \\.-/\n",
+        );
+        let energized = graph.trace(2, 2, Up);
+        assert_eq!(energized.len(), 10);
+        assert!(energized.loops());
+    }
+
+    #[test]
+    fn test_splitters() {
+        let graph = graph(
+            "\
+            ..|..\n\
+            .....\n\
+            ..-..\n",
+        );
+        let energized = graph.trace(0, 0, Right);
+        // right to the splitter, down the middle column, then split along the bottom row.
+        assert_eq!(energized.len(), 2 + 2 + 5);
+        assert!(!energized.loops());
+
+        let from_below = graph.trace(2, 2, Up);
+        assert_eq!(from_below.len(), 5);
+    }
+
+    /// Plain search over beam states, to check the segment graph against.
+    fn naive_energized(graph: &BeamGraph, row: usize, col: usize, direction: Direction) -> usize {
+        let mut seen = vec![false; graph.rows * graph.cols * 4];
+        let mut cells = std::collections::HashSet::new();
+        let mut stack = vec![graph.state(row, col, direction)];
+
+        while let Some(state) = stack.pop() {
+            if !seen[state] {
+                seen[state] = true;
+                cells.insert(state / 4);
+                stack.extend(graph.next_states(state));
+            }
+        }
+
+        cells.len()
+    }
+
+    #[test]
+    fn test_matches_naive_trace() {
+        let mut seed = 42u64;
+        let input: String = (0..12)
+            .map(|_| {
+                (0..15)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                        ['.', '.', '.', '/', '\\', '|', '-', '#'][(seed >> 33) as usize % 8]
+                    })
+                    .chain(std::iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect();
+        let grid: CustomGrid<char> = input_to_grid(&input).unwrap();
+        let graph = BeamGraph::new(&grid, mirrors);
+
+        for row in 0..grid.rows() {
+            for col in 0..grid.cols() {
+                for direction in DIRECTIONS {
+                    assert_eq!(
+                        graph.trace(row, col, direction).len(),
+                        naive_energized(&graph, row, col, direction),
+                        "from {row},{col} {direction:?}"
+                    );
+                }
+            }
+        }
+    }
+}
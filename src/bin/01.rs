@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use advent_of_code::string_search::AhoCorasick;
 use lazy_static::lazy_static;
 use rayon::prelude::*;
 advent_of_code::solution!(1);
@@ -20,40 +20,27 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(response)
 }
 
+const DIGITS: [&str; 18] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9",
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
 lazy_static! {
-    static ref CARD_VALUES: HashMap<&'static str, u8> = HashMap::from([
-        ("1", 1),
-        ("2", 2),
-        ("3", 3),
-        ("4", 4),
-        ("5", 5),
-        ("6", 6),
-        ("7", 7),
-        ("8", 8),
-        ("9", 9),
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9),
-    ]);
+    static ref DIGIT_SEARCH: AhoCorasick = AhoCorasick::new(DIGITS);
 }
 
+fn digit_value(pattern_id: usize) -> u32 {
+    (pattern_id % 9 + 1) as u32
+}
 
 pub fn part_two(input: &str) -> Option<u32> {
     let response = input
         .par_lines()
         .map(|line| {
-            let findings: Vec<_> = CARD_VALUES.iter().map(|(s, val)| (*val, line.find(s), line.rfind(s))).filter(|(_, min, _)| min.is_some()).collect();
-            let min = findings.iter().min_by(|(_, min_index, _), (_, other_min_index, _)| min_index.cmp(other_min_index));
-            let max = findings.iter().max_by(|(_, _, max_index), (_, _, other_max_index)| max_index.cmp(other_max_index));
-
+            let (_, first) = DIGIT_SEARCH.find_first(line).unwrap();
+            let (_, last) = DIGIT_SEARCH.find_last(line).unwrap();
 
-            (min.unwrap().0 * 10 + max.unwrap().0) as u32
+            digit_value(first) * 10 + digit_value(last)
         })
         .sum();
     Some(response)
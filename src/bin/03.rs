@@ -1,48 +1,22 @@
-use advent_of_code::custom_grid::{input_to_grid, CustomGrid};
-use itertools::Itertools;
+use advent_of_code::custom_grid::{input_to_grid, CustomGrid, Span};
 use std::collections::BTreeMap;
-use std::iter::once;
 advent_of_code::solution!(3);
 
+fn number_spans(grid: &CustomGrid<char>) -> Vec<Span<String>> {
+    grid.horizontal_spans(char::is_ascii_digit)
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let grid: CustomGrid<char> = input_to_grid::<char>(input).unwrap();
 
-    let mut iterator = grid.indexed_iter().peekable();
-    let mut sum = 0;
-
-    while let Some(((row, col), value)) = iterator.next() {
-        match *value {
-            _ if value.is_ascii_digit() => {}
-            _ => continue,
-        }
-
-        let group: Vec<_> = once(((row, col), value))
-            .chain(iterator.peeking_take_while(|(_, value)| value.is_ascii_digit()))
-            .collect();
-
-        let symbol = group
-            .iter()
-            .filter_map(|&((row, col), _)| {
-                grid.iter_diagonal_neighbors(row, col)
-                    .filter(|(_, &neighbor_value)| {
-                        neighbor_value != '.' && !neighbor_value.is_ascii_digit()
-                    })
-                    .map(|(pos, _)| pos)
-                    .next()
-            })
-            .next();
-
-        if symbol.is_some() {
-            let group_sum: u32 = group
-                .into_iter()
-                .map(|(_, value)| value)
-                .collect::<String>()
-                .parse()
-                .unwrap();
-
-            sum += group_sum;
-        }
-    }
+    let sum = number_spans(&grid)
+        .into_iter()
+        .filter(|span| {
+            span.border(&grid)
+                .any(|(_, &value)| value != '.' && !value.is_ascii_digit())
+        })
+        .map(|span| span.value.parse::<u32>().unwrap())
+        .sum();
 
     Some(sum)
 }
@@ -50,38 +24,17 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let grid: CustomGrid<char> = input_to_grid::<char>(input).unwrap();
 
-    let mut iterator = grid.indexed_iter().peekable();
     let mut gears: BTreeMap<(usize, usize), Vec<u32>> = BTreeMap::new();
 
-    while let Some(((row, col), value)) = iterator.next() {
-        match *value {
-            _ if value.is_ascii_digit() => {}
-            _ => continue,
-        }
-
-        let group: Vec<_> = once(((row, col), value))
-            .chain(iterator.peeking_take_while(|(_, value)| value.is_ascii_digit()))
-            .collect();
-
-        let gear = group
-            .iter()
-            .filter_map(|&((row, col), _)| {
-                grid.iter_diagonal_neighbors(row, col)
-                    .filter(|(_, &neighbor_value)| neighbor_value == '*')
-                    .map(|(pos, _)| pos)
-                    .next()
-            })
-            .next();
+    for span in number_spans(&grid) {
+        let gear = span.cells().find_map(|(row, col)| {
+            grid.iter_diagonal_neighbors(row, col)
+                .find(|(_, &value)| value == '*')
+                .map(|(pos, _)| pos)
+        });
 
         if let Some(gear) = gear {
-            let group_sum: u32 = group
-                .into_iter()
-                .map(|(_, value)| value)
-                .collect::<String>()
-                .parse()
-                .unwrap();
-
-            gears.entry(gear).or_default().push(group_sum);
+            gears.entry(gear).or_default().push(span.value.parse().unwrap());
         }
     }
 
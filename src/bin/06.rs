@@ -1,3 +1,4 @@
+use advent_of_code::math::quadratic_positive_interval;
 advent_of_code::solution!(6);
 
 struct Race {
@@ -28,12 +29,9 @@ fn parse_input_1(input: &str) -> Vec<Race> {
 }
 
 fn solve_race(race: &Race) -> u64 {
-    let t = race.time as f64;
-    let d = race.distance as f64;
-    let x_min = (0.5f64 * (t - (t * t - 4f64 * d).sqrt())).floor() as u64 + 1;
-    let x_max = (0.5f64 * (t + (t * t - 4f64 * d).sqrt())).ceil() as u64 - 1;
-
-    x_max - x_min + 1
+    // holding the button for `x` ms wins when `x * (time - x) > distance`.
+    quadratic_positive_interval(-1, race.time as i128, -(race.distance as i128))
+        .map_or(0, |holds| (holds.end() - holds.start() + 1) as u64)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -1,4 +1,4 @@
-use num::integer::lcm;
+use advent_of_code::math::first_common_time;
 use std::collections::HashMap;
 advent_of_code::solution!(8);
 
@@ -64,16 +64,16 @@ pub fn part_two(input: &str) -> Option<u64> {
         .map(|(node, _)| *node)
         .collect();
 
-    let mut node_cycle: Vec<Option<u64>> = (0..current_nodes.len()).map(|_| None).collect();
+    let mut z_hits: Vec<Vec<u64>> = vec![vec![]; current_nodes.len()];
 
     for (n, direction) in directions.iter().cycle().enumerate() {
         for (i, node) in current_nodes.iter().enumerate() {
-            if node.ends_with('Z') {
-                node_cycle[i] = Some(n as u64);
+            if node.ends_with('Z') && z_hits[i].len() < 2 {
+                z_hits[i].push(n as u64);
             }
         }
 
-        if node_cycle.iter().all(|cycle| cycle.is_some()) {
+        if z_hits.iter().all(|hits| hits.len() == 2) {
             break;
         }
 
@@ -82,16 +82,45 @@ pub fn part_two(input: &str) -> Option<u64> {
         }
     }
 
-    Some(
-        node_cycle
-            .into_iter()
-            .fold(1u64, |acc, cycle| lcm(acc, cycle.unwrap())),
-    )
+    // each ghost first reaches a Z node at `hits[0]`, then every `hits[1] - hits[0]` steps.
+    let cycles: Vec<_> = z_hits
+        .iter()
+        .map(|hits| (hits[0], hits[1] - hits[0]))
+        .collect();
+
+    first_common_time(&cycles)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::graphviz::Graph;
+
+    fn node_graph(nodes: &HashMap<(&str, Direction), &str>) -> Graph {
+        let mut graph = Graph::directed("nodes");
+        graph
+            .style("start", &[("shape", "box"), ("color", "forestgreen")])
+            .style("end", &[("shape", "doublecircle"), ("color", "firebrick")]);
+
+        let mut edges: Vec<_> = nodes.iter().collect();
+        edges.sort_by_key(|((node, direction), _)| (*node, *direction == Direction::Right));
+
+        for ((node, direction), next) in edges {
+            if node.ends_with('A') {
+                graph.node(node, Some("start"));
+            } else if node.ends_with('Z') {
+                graph.node(node, Some("end"));
+            }
+
+            let label = match direction {
+                Direction::Left => "L",
+                Direction::Right => "R",
+            };
+            graph.edge(node, next, Some(label));
+        }
+
+        graph
+    }
 
     #[test]
     fn test_part_one() {
@@ -106,4 +135,23 @@ mod tests {
         ));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_node_graph() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let (_, nodes) = parse_input(&input);
+        let graph = node_graph(&nodes);
+        assert_eq!(graph.node_count(), 7);
+        assert_eq!(graph.edge_count(), 14);
+        assert!(graph.to_string().contains("\"AAA\" -> \"CCC\" [label=\"R\"];"));
+    }
+
+    /// Writes the node map of the real input to `data/visuals/08/nodes.dot`.
+    #[test]
+    #[ignore]
+    fn export_node_graph() {
+        let input = advent_of_code::template::read_file("inputs", DAY);
+        let (_, nodes) = parse_input(&input);
+        node_graph(&nodes).write(DAY, "nodes").unwrap();
+    }
 }
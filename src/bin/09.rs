@@ -1,4 +1,4 @@
-use tailcall::tailcall;
+use advent_of_code::math::extrapolate;
 advent_of_code::solution!(9);
 
 pub fn parse_input(input: &str) -> Vec<Vec<i32>> {
@@ -12,40 +12,12 @@ pub fn parse_input(input: &str) -> Vec<Vec<i32>> {
         .collect()
 }
 
-fn serie_gaps(serie: &[i32]) -> Vec<i32> {
-    serie
-        .windows(2)
-        .map(|slice| match slice {
-            &[a, b] => b - a,
-            _ => unreachable!(), // Handle other slice patterns if needed
-        })
-        .collect()
-}
-
-fn find_next(serie: &Vec<i32>) -> i32 {
-    #[tailcall]
-    fn find_next_inner(serie: &Vec<i32>) -> i32 {
-        let gaps = serie_gaps(serie);
-        if gaps.iter().all(|&n| n == 0) {
-            *serie.last().expect("No more element in serie")
-        } else {
-            serie.last().expect("No more element in serie") + find_next(&gaps)
-        }
-    }
-    find_next_inner(serie)
+fn find_next(serie: &[i32]) -> i32 {
+    extrapolate(serie, serie.len() as i128) as i32
 }
 
-fn find_previous(serie: &Vec<i32>) -> i32 {
-    #[tailcall]
-    fn find_previous_inner(serie: &Vec<i32>) -> i32 {
-        let gaps = serie_gaps(serie);
-        if gaps.iter().all(|&n| n == 0) {
-            *serie.first().expect("No more element in serie")
-        } else {
-            serie.first().expect("No more element in serie") - find_previous(&gaps)
-        }
-    }
-    find_previous_inner(serie)
+fn find_previous(serie: &[i32]) -> i32 {
+    extrapolate(serie, -1) as i32
 }
 
 pub fn part_one(input: &str) -> Option<i32> {
@@ -1,5 +1,5 @@
 use advent_of_code::custom_grid::{input_to_grid, CustomGrid};
-use std::collections::HashMap;
+use advent_of_code::geometry::Polygon;
 advent_of_code::solution!(10);
 
 #[derive(Clone, Copy, PartialEq)]
@@ -105,48 +105,6 @@ fn replace_starting_point(
     }
 }
 
-fn count_inside_space(grid: &CustomGrid<char>) -> u32 {
-    #[derive(Debug, PartialEq)]
-    enum State {
-        Outside,
-        Inside,
-        BottomCorner(bool),
-        TopCorner(bool),
-    }
-    let mut count = 0;
-
-    // Knot theory magic
-    for row in 0..grid.rows() {
-        let mut state = State::Outside;
-        for col in 0..grid.cols() {
-            state = match (state, grid.get(row, col).unwrap()) {
-                (State::Outside, '│') => State::Inside,
-                (State::Inside, '│') => State::Outside,
-                (State::Inside, ' ') => {
-                    count += 1;
-                    State::Inside
-                }
-                (State::Outside, '┌') => State::BottomCorner(false),
-                (State::Outside, '└') => State::TopCorner(false),
-                (State::Inside, '┌') => State::BottomCorner(true),
-                (State::Inside, '└') => State::TopCorner(true),
-                (State::BottomCorner(inside), '─') => State::BottomCorner(inside),
-                (State::BottomCorner(true), '┐') => State::Inside,
-                (State::BottomCorner(true), '┘') => State::Outside,
-                (State::BottomCorner(false), '┐') => State::Outside,
-                (State::BottomCorner(false), '┘') => State::Inside,
-                (State::TopCorner(inside), '─') => State::TopCorner(inside),
-                (State::TopCorner(true), '┐') => State::Outside,
-                (State::TopCorner(true), '┘') => State::Inside,
-                (State::TopCorner(false), '┐') => State::Inside,
-                (State::TopCorner(false), '┘') => State::Outside,
-                (state, _) => state,
-            };
-        }
-    }
-    count
-}
-
 pub fn part_one(input: &str) -> Option<u32> {
     let mut grid: CustomGrid<char> = input_to_grid(input).unwrap();
 
@@ -178,11 +136,11 @@ pub fn part_two(input: &str) -> Option<u32> {
     replace_with_box_char(&mut grid);
     let start_direction = replace_starting_point(&mut grid, start_row, start_col);
 
-    let mut path = HashMap::from([((start_row, start_col), true)]);
+    let mut path = vec![];
     let (mut next_row, mut next_col, mut direction) = (start_row, start_col, start_direction);
 
     loop {
-        path.insert((next_row, next_col), true);
+        path.push((next_row as i64, next_col as i64));
         (next_row, next_col, direction) = step(&grid, next_row, next_col, direction);
 
         if (next_row, next_col) == (start_row, start_col) {
@@ -190,16 +148,8 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    // Remove everything not in path
-    for row in 0..grid.rows() {
-        for col in 0..grid.cols() {
-            if !path.contains_key(&(row, col)) {
-                *grid.get_mut(row, col).unwrap() = ' ';
-            }
-        }
-    }
-
-    Some(count_inside_space(&grid))
+    // every loop tile is a polygon vertex, so the enclosed tiles are its interior lattice points.
+    Some(Polygon::from_vertices(path).interior_points() as u32)
 }
 
 #[cfg(test)]
@@ -1,81 +1,32 @@
+use advent_of_code::compression::CompressedPoints;
 use advent_of_code::custom_grid::{input_to_grid, CustomGrid};
-use itertools::Itertools;
-use std::collections::HashMap;
 
 advent_of_code::solution!(11);
 
-fn parse_input(input: &str, gap: usize) -> Vec<(usize, usize)> {
+fn parse_input(input: &str) -> Vec<(i64, i64)> {
     let grid: CustomGrid<char> = input_to_grid(input).unwrap();
 
-    // For each row and cols, how many empty row/col is there ahead of it.
-    // It is to know how much it should shift any galaxy at this row/col
-    let mut empty_rows: HashMap<usize, usize> = HashMap::new();
-    let mut empty_cols: HashMap<usize, usize> = HashMap::new();
-
-    let mut current_empty_rows = 0;
-
-    for (i, mut row) in grid.iter_rows().enumerate() {
-        if row.all(|&n| n == '.') {
-            current_empty_rows += 1;
-        }
-
-        empty_rows.insert(i, current_empty_rows);
-    }
-
-    let mut current_empty_cols = 0;
-
-    for (i, mut col) in grid.iter_cols().enumerate() {
-        if !col.contains(&'#') {
-            current_empty_cols += 1;
-        }
-
-        empty_cols.insert(i, current_empty_cols);
-    }
-
     grid.indexed_iter()
         .filter_map(|((row, col), c)| match c {
-            '#' => Some((
-                row + (gap - 1) * empty_rows.get(&row).expect("no empty row entry in map"),
-                col + (gap - 1) * empty_cols.get(&col).expect("no empty col entry in map"),
-            )),
+            '#' => Some((row as i64, col as i64)),
             _ => None,
         })
         .collect()
 }
 
-fn distance((row, col): (usize, usize), (other_row, other_col): (usize, usize)) -> usize {
-    row.abs_diff(other_row) + col.abs_diff(other_col)
+/// Sum of the distances between every pair of galaxies, once each empty row and column has been
+/// replaced by `gap` of them.
+fn solve(input: &str, gap: i64) -> i64 {
+    let galaxies = parse_input(input);
+    CompressedPoints::new(&galaxies, gap, gap).pairwise_manhattan_sum()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        parse_input(input, 2)
-            .into_iter()
-            .combinations(2)
-            .map(|chunk| {
-                let (row, col) = chunk.first().unwrap();
-                let (other_row, other_col) = chunk.get(1).unwrap();
-
-                distance((*row, *col), (*other_row, *other_col)) as u32
-            })
-            .sum(),
-    )
+    Some(solve(input, 2) as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let positions = parse_input(input, 1_000_000);
-    Some(
-        positions
-            .iter()
-            .combinations(2)
-            .map(|chunk| {
-                let (row, col) = chunk.first().unwrap();
-                let (other_row, other_col) = chunk.get(1).unwrap();
-
-                distance((*row, *col), (*other_row, *other_col)) as u64
-            })
-            .sum(),
-    )
+    Some(solve(input, 1_000_000) as u64)
 }
 
 #[cfg(test)]
@@ -83,11 +34,21 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_distance() {
-        assert_eq!(distance((6, 1), (11, 5)), 9);
-        assert_eq!(distance((0, 0), (0, 0)), 0);
-        assert_eq!(distance((0, 0), (0, 1)), 1);
-        assert_eq!(distance((0, 0), (1, 1)), 2);
+    fn test_expansion() {
+        let galaxies = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let points = CompressedPoints::new(&galaxies, 2, 2);
+
+        // galaxies 5 and 9 of the puzzle description, in the expanded universe.
+        let weighted: Vec<_> = points.weighted().collect();
+        assert_eq!(weighted[4], (6, 1));
+        assert_eq!(weighted[8], (11, 5));
+
+        let original: Vec<_> = points
+            .compressed()
+            .iter()
+            .map(|&p| points.original(p))
+            .collect();
+        assert_eq!(original, galaxies);
     }
 
     #[test]
@@ -1,4 +1,4 @@
-use memoize::memoize;
+use advent_of_code::nonogram::count_arrangements;
 use rayon::prelude::*;
 
 advent_of_code::solution!(12);
@@ -6,10 +6,10 @@ advent_of_code::solution!(12);
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 struct Row<'a> {
     pattern: &'a str,
-    consecutives: Vec<u8>,
+    consecutives: Vec<usize>,
 }
 
-fn parse_input(input: &str) -> Vec<Row> {
+fn parse_input(input: &str) -> Vec<Row<'_>> {
     input
         .lines()
         .map(|line| {
@@ -28,181 +28,44 @@ fn parse_input(input: &str) -> Vec<Row> {
         .collect()
 }
 
-#[memoize]
-fn solve_row(row: Row<'static>) -> u64 {
-    // let row_clone = row.clone();
-    // println!("solving {:?}", &row);
-    if row.consecutives.is_empty() {
-        return match row.pattern.contains('#') {
-            true => {
-                // println!("{row_clone:?}");
-                // println!("return 0 (consecutive empty, # remaining)");
-                0
-            }
-            false => {
-                // println!("{row_clone:?}");
-                // println!("return 1 (consecutive empty, no #)");
-                1
-            }
-        };
-    }
-
-    if row.pattern.is_empty() {
-        // println!("{row_clone:?}");
-        // println!("return 0 (pattern empty)");
-        return 0;
-    }
-
-    let mut row = row.clone();
-    row.pattern = row.pattern.trim_end_matches('.'); // Remove trailing dots
-    let consecutive = row.consecutives.pop().unwrap() as usize;
-
-    let last_dot_pos = row.pattern.rfind('.');
-
-    let left_space = match last_dot_pos {
-        None => row.pattern.len(),
-        Some(last_dot_pos) => row.pattern.len() - last_dot_pos - 1,
-    };
-
-    if left_space < consecutive {
-        return match last_dot_pos {
-            None => {
-                // println!("{row_clone:?}");
-                // println!("return 0 (no space left for a {consecutive} consecutive)");
-                0
-            }
-            Some(last_dot_pos) => {
-                if row.pattern[last_dot_pos..].contains('#') {
-                    // println!("{row_clone:?}");
-                    // println!("return 0 (cannot include # in a consecutive)");
-                    0
-                } else {
-                    let mut consecutive_with_popped = row.consecutives.clone();
-                    consecutive_with_popped.push(consecutive as u8);
-
-                    // println!("{row_clone:?}");
-                    // println!("return {sub_solve} (no solution, went to subsolve for {:?})", &row.pattern[..last_dot_pos]);
-
-                    solve_row(Row {
-                        consecutives: consecutive_with_popped,
-                        pattern: &row.pattern[..last_dot_pos],
-                    })
-                }
-            }
-        };
-    }
-
-    let current = &row.pattern[row.pattern.len() - left_space..];
-    let rest = &row.pattern[..row.pattern.len() - left_space];
-
-    if left_space == consecutive {
-        let sub_case_replace_by_hashtag = Row {
-            consecutives: row.consecutives.clone(),
-            pattern: rest,
-        };
-
-        return if current.chars().all(|c| c.eq(&'?')) {
-            let mut consecutive_with_popped = row.consecutives.clone();
-            consecutive_with_popped.push(consecutive as u8);
-            let sub_case_replace_by_dot = Row {
-                consecutives: consecutive_with_popped,
-                pattern: rest,
-            };
-
-            let sub_case_replace_by_dot_solve = solve_row(sub_case_replace_by_dot);
-            let sub_case_replace_by_hashtag_solve = solve_row(sub_case_replace_by_hashtag);
-
-            // println!("{row_clone:?}");
-            // println!("return {sub_case_replace_by_dot_solve} + {sub_case_replace_by_hashtag_solve} (dot + hashtag)");
-            sub_case_replace_by_dot_solve + sub_case_replace_by_hashtag_solve
-        } else {
-            // println!("{row_clone:?}");
-            // println!("return sub_case_replace_by_hashtag_solve (hashtag)");
-
-            solve_row(sub_case_replace_by_hashtag)
-        };
-    }
-
-    let possibilities = left_space - consecutive + 1;
-    let start_looking = row.pattern.len() - left_space;
-    let end_looking = start_looking + possibilities;
-
-    let mut total = 0;
-
-    // Consider replacing everything by dots
-    if let Some(last_dot_pos) = last_dot_pos {
-        if !row.pattern[last_dot_pos..].contains('#') {
-            let mut consecutive_with_popped = row.consecutives.clone();
-            consecutive_with_popped.push(consecutive as u8);
-            let sub_solve = solve_row(Row {
-                consecutives: consecutive_with_popped,
-                pattern: &row.pattern[..last_dot_pos],
-            });
-
-            total += sub_solve;
-        }
-    }
-
-    for i in start_looking..end_looking {
-        let before = match i {
-            0 => '.',
-            i => row.pattern.chars().nth(i - 1).unwrap(),
-        };
-        let after = &row.pattern[i + consecutive..];
-        // println!("{i} before: {before} after: {after}");
-        if (before == '?' || before == '.') && after.chars().all(|c| c.eq(&'?')) {
-            let sub_row = match i {
-                0 => Row {
-                    consecutives: row.consecutives.clone(),
-                    pattern: "",
-                },
-                i => Row {
-                    consecutives: row.consecutives.clone(),
-                    pattern: &row.pattern[..i - 1],
-                },
-            };
-
-            total += solve_row(sub_row)
-        }
-    }
-
-    // println!("{row_clone:?}");
-    // println!("return sum of {:?}: {}", &total, total.iter().sum::<u64>());
-    total
+fn solve_row(row: Row) -> u64 {
+    let pattern: Vec<char> = row.pattern.chars().collect();
+    count_arrangements(&pattern, &row.consecutives)
 }
 
-fn solve(rows: Vec<Row<'static>>) -> u64 {
-    rows.par_iter()
-        .map(|row| {
-            // println!("{:?} {:?} {}", row.pattern, row.consecutives, solve);
-            solve_row(row.clone())
-        })
-        .sum()
+fn solve(rows: Vec<Row>) -> u64 {
+    rows.into_par_iter().map(solve_row).sum()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let input = input.to_string().leak();
     let input = parse_input(input);
 
     Some(solve(input))
 }
 
-fn complicate_things(input: &mut [Row]) {
-    for row in input.iter_mut() {
-        let mut duplicated = format!("{}?", row.pattern).repeat(5);
-        duplicated.pop(); // remove trailing ?
-        row.pattern = duplicated.leak();
-        row.consecutives = row.consecutives.repeat(5);
-
-        // println!("{:?}", row);
-    }
+fn complicate_things(input: &[Row]) -> Vec<String> {
+    input
+        .iter()
+        .map(|row| {
+            let mut duplicated = format!("{}?", row.pattern).repeat(5);
+            duplicated.pop(); // remove trailing ?
+            duplicated
+        })
+        .collect()
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let input = input.to_string().leak();
-    let mut input = parse_input(input);
+    let input = parse_input(input);
 
-    complicate_things(&mut input);
+    let patterns = complicate_things(&input);
+    let input = input
+        .into_iter()
+        .zip(&patterns)
+        .map(|(row, pattern)| Row {
+            pattern,
+            consecutives: row.consecutives.repeat(5),
+        })
+        .collect();
 
     Some(solve(input))
 }
@@ -3,10 +3,8 @@
 use advent_of_code::custom_grid::{input_to_grid, CustomGrid};
 use itertools::Itertools;
 use std::collections::HashMap;
-use memoize::memoize;
 advent_of_code::solution!(14);
 
-#[memoize]
 fn cycle(mut grid: CustomGrid<char>) -> CustomGrid<char> {
     tilt_up(&mut grid);
     tilt_left(&mut grid);
@@ -137,10 +135,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let input = input.to_string().leak();
     let mut grid: CustomGrid<char> = input_to_grid(input).unwrap();
 
-
     let mut memo = HashMap::new();
     let mut solves = HashMap::new();
     let mut i = 0usize;
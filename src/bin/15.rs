@@ -1,10 +1,8 @@
-use itertools::Itertools;
+use advent_of_code::holiday_hash::{holiday_hash, LensMap};
 advent_of_code::solution!(15);
 
 fn hash(input: &str) -> u32 {
-    input
-        .chars()
-        .fold(0, |acc, c| (17 * (acc + c as u32)) % 256)
+    holiday_hash(input.as_bytes()) as u32
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -41,52 +39,22 @@ fn parse_entry(str: &str) -> Entry {
 pub fn part_two(input: &str) -> Option<u32> {
     // let input = input.to_string().leak();
 
-    let sum = input
-        .trim()
-        .split(',')
-        .map(parse_entry)
-        .fold(vec![vec![]; 256], |mut boxes: Vec<Vec<Entry>>, entry| {
-            let hash = hash(entry.label) as usize;
-            let current_box = boxes.get_mut(hash).unwrap();
-
-            let entry_with_same_label =
-                current_box.iter().find_position(|e| e.label == entry.label);
-
-            match &entry.operation_char {
+    let lenses = input.trim().split(',').map(parse_entry).fold(
+        LensMap::new(),
+        |mut lenses, entry| {
+            match entry.operation_char {
                 OperationChar::Dash => {
-                    if let Some((same_label_entry_position, _)) = entry_with_same_label {
-                        current_box.remove(same_label_entry_position);
-                    }
+                    lenses.remove(&entry.label);
                 }
-                OperationChar::Equal(_) => {
-                    if let Some((same_label_entry_position, _)) = entry_with_same_label {
-                        current_box.push(entry);
-                        current_box.swap_remove(same_label_entry_position);
-                    } else {
-                        current_box.push(entry);
-                    }
+                OperationChar::Equal(focal_length) => {
+                    lenses.insert(entry.label, focal_length);
                 }
             }
-            boxes
-        })
-        .iter()
-        .enumerate()
-        .map(|(i, b)| {
-            (i + 1) as u32
-                * b.iter()
-                    .enumerate()
-                    .map(|(ii, entry)| {
-                        (ii + 1) as u32
-                            * match entry.operation_char {
-                                OperationChar::Dash => 0,
-                                OperationChar::Equal(fl) => fl as u32,
-                            }
-                    })
-                    .sum::<u32>()
-        })
-        .sum();
+            lenses
+        },
+    );
 
-    Some(sum)
+    Some(lenses.focusing_power() as u32)
 }
 
 #[cfg(test)]
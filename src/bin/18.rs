@@ -1,5 +1,6 @@
 use advent_of_code::custom_grid::Direction;
 use advent_of_code::custom_grid::Direction::{Down, Left, Right, Up};
+use advent_of_code::geometry::Polygon;
 
 advent_of_code::solution!(18);
 
@@ -51,35 +52,9 @@ fn parse_input_part_2(input: &str) -> Vec<Line<u64>> {
 }
 
 fn solve(lines: Vec<Line<u64>>) -> u64 {
-    let (_, mut corners) =
-        lines
-            .iter()
-            .fold(((0isize, 0isize), vec![]), |((mut row, mut col), mut points), Line { n, direction }| {
-                points.push((row, col));
-                match direction {
-                    Up => row -= *n as isize,
-                    Down => row += *n as isize,
-                    Right => col += *n as isize,
-                    Left => col -= *n as isize
-                }
+    let lagoon = Polygon::from_steps(lines.iter().map(|Line { direction, n }| (*direction, *n as i64)));
 
-                ((row, col), points)
-            });
-
-    corners.push((0, 0));
-    let (inside_points, border_points) = corners.windows(2).fold((0i64, 0i64), |(acc_ip, acc_bp), window| {
-        if let [(row, col), (next_row, next_col)] = window {
-            (
-                acc_ip + (col + next_col) as i64 * (row - next_row) as i64,
-                acc_bp + row.abs_diff(*next_row) as i64 + col.abs_diff(*next_col) as i64
-            )
-
-        } else {
-            unreachable!()
-        }
-    });
-
-    ((inside_points.abs() + border_points) /2 + 1) as u64
+    (lagoon.interior_points() + lagoon.boundary_points()) as u64
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -246,6 +246,44 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::graphviz::Graph;
+
+    fn workflow_graph(rules: &HashMap<&str, (Vec<Rule>, Next)>) -> Graph {
+        fn target<'a>(next: &Next<'a>) -> &'a str {
+            match *next {
+                Next::Accepted => "A",
+                Next::Rejected => "R",
+                Next::Rule(name) => name,
+            }
+        }
+
+        let mut graph = Graph::directed("workflows");
+        graph
+            .style("start", &[("shape", "box"), ("style", "bold")])
+            .style("accepted", &[("shape", "doublecircle"), ("color", "forestgreen")])
+            .style("rejected", &[("shape", "doublecircle"), ("color", "firebrick")])
+            .node("in", Some("start"))
+            .node("A", Some("accepted"))
+            .node("R", Some("rejected"));
+
+        let mut names: Vec<_> = rules.keys().collect();
+        names.sort();
+
+        for name in names {
+            let (workflow, fallback) = &rules[name];
+            for rule in workflow {
+                let comp = match rule.comp {
+                    Comp::GreaterThan => '>',
+                    Comp::LowerThan => '<',
+                };
+                let label = format!("{}{comp}{}", rule.input_value, rule.value);
+                graph.edge(name, target(&rule.next_ok), Some(&label));
+            }
+            graph.edge(name, target(fallback), Some("else"));
+        }
+
+        graph
+    }
 
     #[test]
     fn test_part_one() {
@@ -258,4 +296,23 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(167409079868000));
     }
+
+    #[test]
+    fn test_workflow_graph() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let (rules, _) = parse_input(&input);
+        let graph = workflow_graph(&rules);
+        assert_eq!(graph.node_count(), 13);
+        assert!(graph.to_string().contains("\"px\" -> \"qkq\" [label=\"a<2006\"];"));
+        assert!(graph.to_string().contains("\"px\" -> \"rfg\" [label=\"else\"];"));
+    }
+
+    /// Writes the workflows of the real input to `data/visuals/19/workflows.dot`.
+    #[test]
+    #[ignore]
+    fn export_workflow_graph() {
+        let input = advent_of_code::template::read_file("inputs", DAY);
+        let (rules, _) = parse_input(&input);
+        workflow_graph(&rules).write(DAY, "workflows").unwrap();
+    }
 }
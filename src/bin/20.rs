@@ -1,5 +1,5 @@
 use std::collections::{HashMap, VecDeque};
-use num::integer::lcm;
+use advent_of_code::math::first_common_time;
 advent_of_code::solution!(20);
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
@@ -287,16 +287,44 @@ pub fn part_two(input: &str) -> Option<u64> {
         }
     }
 
-    Some(
-        values
-            .into_iter()
-            .fold(1u64, lcm),
-    )
+    // each counter resets once it fires, so it fires again `n` presses later.
+    let cycles: Vec<_> = values.into_iter().map(|n| (n, n)).collect();
+
+    first_common_time(&cycles)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::graphviz::Graph;
+
+    fn module_graph(modules: &HashMap<&str, Module>) -> Graph {
+        let mut graph = Graph::directed("modules");
+        graph
+            .style("flip-flop", &[("shape", "box"), ("color", "steelblue")])
+            .style("conjunction", &[("shape", "diamond"), ("color", "darkorange")])
+            .style("broadcast", &[("shape", "doublecircle")])
+            .style("output", &[("shape", "plaintext")]);
+
+        let mut names: Vec<_> = modules.keys().collect();
+        names.sort();
+
+        for name in names {
+            let (kind, outputs) = match &modules[name] {
+                Module::FlipFlop(f) => ("flip-flop", Some(&f.outputs)),
+                Module::Conjunction(c) => ("conjunction", Some(&c.outputs)),
+                Module::Broadcast(b) => ("broadcast", Some(&b.outputs)),
+                Module::Output => ("output", None),
+            };
+
+            graph.node(name, Some(kind));
+            for output in outputs.into_iter().flatten() {
+                graph.edge(name, output, None);
+            }
+        }
+
+        graph
+    }
 
     #[test]
     fn test_part_one() {
@@ -309,4 +337,21 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(1));
     }
+
+    #[test]
+    fn test_module_graph() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let graph = module_graph(&parse_input(&input));
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 6);
+        assert!(graph.to_string().contains("\"inv\" [shape=\"diamond\", color=\"darkorange\"];"));
+    }
+
+    /// Writes the module network of the real input to `data/visuals/20/modules.dot`.
+    #[test]
+    #[ignore]
+    fn export_module_graph() {
+        let input = advent_of_code::template::read_file("inputs", DAY);
+        module_graph(&parse_input(&input)).write(DAY, "modules").unwrap();
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::{BinaryHeap};
+use advent_of_code::math::extrapolate;
 use fxhash::{FxHashMap, FxHashSet};
 use advent_of_code::custom_grid::CustomGrid;
 advent_of_code::solution!(21);
@@ -29,51 +30,53 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(count as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let grid: CustomGrid<char> = input.parse().unwrap();
-
-    let ((start_row, start_col), _) = grid.indexed_iter().find(|(_, &c)| c == 'S').unwrap();
+/// Counts the garden plots reachable in exactly `steps` steps on the infinitely repeated grid,
+/// for each of the requested step counts.
+fn reachable_plots(grid: &CustomGrid<char>, (start_row, start_col): (usize, usize), steps: &[usize]) -> Vec<u64> {
+    let max_steps = steps.iter().copied().max().unwrap_or(0);
+    let (rows, cols) = (grid.rows() as isize, grid.cols() as isize);
 
+    let start = (start_row as isize, start_col as isize);
     let mut visited_at = FxHashMap::default();
-    let mut visited = FxHashSet::default();
-    visited.insert((start_row, start_col));
-    let mut to_visit = BinaryHeap::from([(start_row, start_col)]);
-
-    for i in 0..=130 {
-        let mut to_visit_next = BinaryHeap::new();
-        while let Some((row, col)) = to_visit.pop() {
-            if grid.is_border(row, col) {
-                // println!("{row}, {col} visited at {i}");
-            }
-            visited_at.insert((row, col), i);
-            for ((neighbor_row, neighbor_col), _) in grid.iter_neighbors(row, col).filter(|(_, &c)| c == '.') {
-                if !visited.contains(&(neighbor_row, neighbor_col)) {
-                    visited.insert((neighbor_row, neighbor_col));
+    visited_at.insert(start, 0);
+    let mut to_visit = vec![start];
+
+    for i in 1..=max_steps {
+        let mut to_visit_next = vec![];
+        for (row, col) in to_visit {
+            for (neighbor_row, neighbor_col) in [(row - 1, col), (row + 1, col), (row, col - 1), (row, col + 1)] {
+                let plot = grid.get(neighbor_row.rem_euclid(rows) as usize, neighbor_col.rem_euclid(cols) as usize);
+                if plot != Some(&'#') && !visited_at.contains_key(&(neighbor_row, neighbor_col)) {
+                    visited_at.insert((neighbor_row, neighbor_col), i);
                     to_visit_next.push((neighbor_row, neighbor_col));
                 }
             }
         }
-
-        let mut grid_clone = grid.clone();
-        for (row, col) in visited.iter() {
-            *grid_clone.get_mut(*row, *col).unwrap() = '0';
-        }
-        for (row, col) in to_visit.iter() {
-            *grid_clone.get_mut(*row, *col).unwrap() = 'X';
-        }
-
         to_visit = to_visit_next;
     }
 
-    let even_squares = visited_at.values().filter(|val| **val % 2 == 0).count() as u64;
-    let odd_squares = visited_at.values().filter(|val| **val % 2 == 1).count() as u64;
+    steps
+        .iter()
+        .map(|&steps| visited_at.values().filter(|&&i| i <= steps && i % 2 == steps % 2).count() as u64)
+        .collect()
+}
 
-    let even_corners = visited_at.values().filter(|val| **val % 2 == 0 && **val > 65).count() as u64;
-    let odd_corners = visited_at.values().filter(|val| **val % 2 == 1 && **val > 65).count() as u64;
+pub fn part_two(input: &str) -> Option<u64> {
+    const STEPS: usize = 26501365;
 
-    let n: u64 = (26501365 - 65) / 131;
+    let grid: CustomGrid<char> = input.parse().unwrap();
 
-    Some((n + 1).pow(2) * odd_squares + n.pow(2) * even_squares - (n + 1) * odd_corners + n * even_corners)
+    let (start, _) = grid.indexed_iter().find(|(_, &c)| c == 'S').unwrap();
+
+    // Once the walk reaches the grid edges, each additional grid width walked adds a ring of
+    // repeated grids, so the plot count is quadratic in the number of widths walked.
+    // Sampling starts one width in: gardens without clear paths to the edges need that long to settle.
+    let size = grid.rows();
+    let remainder = STEPS % size;
+    let sample_steps: Vec<_> = (1..=3).map(|widths| remainder + widths * size).collect();
+    let samples = reachable_plots(&grid, start, &sample_steps);
+
+    Some(extrapolate(&samples, (STEPS / size) as i128 - 1) as u64)
 }
 
 #[cfg(test)]
@@ -89,6 +92,22 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(29834683280163));
+        assert_eq!(result, Some(470149643712804));
+    }
+
+    #[test]
+    fn test_extrapolation_matches_walk() {
+        let grid: CustomGrid<char> = advent_of_code::template::read_file("examples", DAY).parse().unwrap();
+        let (start, _) = grid.indexed_iter().find(|(_, &c)| c == 'S').unwrap();
+
+        // the expectation above is only trustworthy if the fitted polynomial predicts the
+        // counts of walks short enough to run, starting at the same remainder as part two.
+        let size = grid.rows();
+        let steps: Vec<_> = (1..=5).map(|widths| 26501365 % size + widths * size).collect();
+        let counts = reachable_plots(&grid, start, &steps);
+
+        for widths in 3..5 {
+            assert_eq!(extrapolate(&counts[..3], widths as i128) as u64, counts[widths]);
+        }
     }
 }
@@ -0,0 +1,178 @@
+//! Configurable card hand ranking for poker-like puzzles.
+//!
+//! A [`HandRules`] holds the card order, the wildcard cards and the category scheme. Hands are
+//! turned into a [`Hand`] whose integer key sorts them: the category first, then each card's rank
+//! in order, as day 7 requires.
+
+/// Maps the sizes of the groups of equal cards, largest first, to a category. Higher is stronger.
+pub type CategoryScheme = fn(&[usize]) -> u8;
+
+/// Camel cards categories: high card (0), one pair, two pair, three of a kind, full house,
+/// four of a kind and five of a kind (6).
+pub fn camel_cards_category(groups: &[usize]) -> u8 {
+    match groups {
+        [5, ..] => 6,
+        [4, ..] => 5,
+        [3, 2, ..] => 4,
+        [3, ..] => 3,
+        [2, 2, ..] => 2,
+        [2, ..] => 1,
+        _ => 0,
+    }
+}
+
+/// A hand's strength under some [`HandRules`], comparable with other hands of the same size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand {
+    key: u64,
+    category: u8,
+}
+
+impl Hand {
+    pub fn category(&self) -> u8 {
+        self.category
+    }
+
+    /// Sort key: one byte for the category followed by one byte per card rank.
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HandRules {
+    /// Cards from weakest to strongest.
+    order: Vec<char>,
+    wildcards: Vec<char>,
+    category: CategoryScheme,
+}
+
+impl HandRules {
+    /// Rules with cards ranked by `order`, weakest first, no wildcard and camel cards categories.
+    pub fn new(order: &str) -> Self {
+        Self {
+            order: order.chars().collect(),
+            wildcards: vec![],
+            category: camel_cards_category,
+        }
+    }
+
+    pub fn camel_cards() -> Self {
+        Self::new("23456789TJQKA")
+    }
+
+    /// Camel cards where `J` is a joker, the weakest card but standing in for any other.
+    pub fn camel_cards_with_jokers() -> Self {
+        Self::new("J23456789TQKA").with_wildcards("J")
+    }
+
+    /// Wildcards join the largest group of other cards, which gives the best category for any
+    /// scheme that rewards bigger groups.
+    pub fn with_wildcards(mut self, wildcards: &str) -> Self {
+        self.wildcards = wildcards.chars().collect();
+        self
+    }
+
+    pub fn with_category(mut self, category: CategoryScheme) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Rank of a card, `0` being the weakest.
+    pub fn rank(&self, card: char) -> Option<u8> {
+        self.order
+            .iter()
+            .position(|&c| c == card)
+            .map(|rank| rank as u8)
+    }
+
+    /// # Panics
+    /// If a card is not part of the order or if the hand has more than 7 cards.
+    pub fn hand(&self, cards: &str) -> Hand {
+        let ranks: Vec<u8> = cards
+            .chars()
+            .map(|card| {
+                self.rank(card)
+                    .unwrap_or_else(|| panic!("unknown card {card}"))
+            })
+            .collect();
+        assert!(ranks.len() < 8, "hands are limited to 7 cards");
+
+        let mut counts = vec![0; self.order.len()];
+        let mut wildcards = 0;
+        for card in cards.chars() {
+            if self.wildcards.contains(&card) {
+                wildcards += 1;
+            } else {
+                counts[self.rank(card).unwrap() as usize] += 1;
+            }
+        }
+
+        let mut groups: Vec<usize> = counts.into_iter().filter(|&count| count > 0).collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        match groups.first_mut() {
+            Some(largest) => *largest += wildcards,
+            None if wildcards > 0 => groups.push(wildcards),
+            None => {}
+        }
+
+        let category = (self.category)(&groups);
+        let key = ranks
+            .iter()
+            .fold(category as u64, |key, &rank| key << 8 | rank as u64);
+
+        Hand { key, category }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categories() {
+        let rules = HandRules::camel_cards();
+        let categories: Vec<_> = [
+            "23456", "A23A4", "23432", "TTT98", "23332", "AA8AA", "AAAAA",
+        ]
+        .iter()
+        .map(|cards| rules.hand(cards).category())
+        .collect();
+        assert_eq!(categories, vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_order_within_category() {
+        let rules = HandRules::camel_cards();
+        assert!(rules.hand("33332") > rules.hand("2AAAA"));
+        assert!(rules.hand("77888") > rules.hand("77788"));
+        assert_eq!(
+            rules.hand("KK677").key(),
+            2 << 40 | 11 << 32 | 11 << 24 | 4 << 16 | 5 << 8 | 5
+        );
+    }
+
+    #[test]
+    fn test_jokers() {
+        let rules = HandRules::camel_cards_with_jokers();
+        assert_eq!(rules.hand("QJJQ2").category(), 5);
+        assert_eq!(rules.hand("JJJJJ").category(), 6);
+        assert_eq!(rules.hand("2345J").category(), 1);
+        assert_eq!(rules.hand("KTJJT").category(), 5);
+        assert!(rules.hand("JKKK2") < rules.hand("QQQQ2"));
+    }
+
+    #[test]
+    fn test_variants() {
+        let aces_low = HandRules::new("A23456789TJQK");
+        assert!(aces_low.hand("A2345") < aces_low.hand("23456"));
+
+        // any two-card group beats a high card, nothing else counts.
+        let pairs_only = HandRules::camel_cards()
+            .with_wildcards("JQ")
+            .with_category(|groups| u8::from(groups.first() >= Some(&2)));
+        assert_eq!(pairs_only.hand("JQ234").category(), 1);
+        assert_eq!(pairs_only.hand("J2345").category(), 1);
+        assert_eq!(pairs_only.hand("K2345").category(), 0);
+    }
+}
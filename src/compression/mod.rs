@@ -0,0 +1,196 @@
+//! Coordinate compression for sparse point sets, with prefix-sum distance sums.
+//!
+//! Each axis keeps the sorted distinct coordinates that hold a point. The empty coordinates
+//! between them can be weighted, so that every empty row of a galaxy map counts as a million
+//! rows, without ever materialising the stretched grid.
+use crate::geometry::Point;
+
+/// The distinct coordinates of one axis, with their position once empty coordinates are weighted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Axis {
+    values: Vec<i64>,
+    positions: Vec<i64>,
+}
+
+impl Axis {
+    /// Compresses `values`, counting each coordinate that holds no value as `gap_weight`.
+    /// Empty coordinates between `0` and the smallest value are weighted too.
+    pub fn new(values: impl IntoIterator<Item = i64>, gap_weight: i64) -> Self {
+        let mut values: Vec<i64> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+
+        let mut positions = Vec::with_capacity(values.len());
+        let mut previous: Option<(i64, i64)> = None;
+        for &value in &values {
+            let position = match previous {
+                None => value * gap_weight,
+                Some((previous_value, previous_position)) => {
+                    previous_position + 1 + (value - previous_value - 1) * gap_weight
+                }
+            };
+            positions.push(position);
+            previous = Some((value, position));
+        }
+
+        Self { values, positions }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    /// Compressed index of an original coordinate, if it holds a value.
+    pub fn index(&self, value: i64) -> Option<usize> {
+        self.values.binary_search(&value).ok()
+    }
+
+    /// Original coordinate of a compressed index.
+    pub fn value(&self, index: usize) -> i64 {
+        self.values[index]
+    }
+
+    /// Weighted coordinate of a compressed index.
+    pub fn position(&self, index: usize) -> i64 {
+        self.positions[index]
+    }
+
+    /// Original distance between a compressed index and the next one, i.e. the size of the
+    /// compressed cell starting at `index`.
+    pub fn width(&self, index: usize) -> i64 {
+        self.values[index + 1] - self.values[index]
+    }
+}
+
+/// A point set compressed on both axes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompressedPoints {
+    rows: Axis,
+    cols: Axis,
+    points: Vec<(usize, usize)>,
+}
+
+impl CompressedPoints {
+    pub fn new(points: &[Point], row_gap_weight: i64, col_gap_weight: i64) -> Self {
+        let rows = Axis::new(points.iter().map(|&(row, _)| row), row_gap_weight);
+        let cols = Axis::new(points.iter().map(|&(_, col)| col), col_gap_weight);
+        let points = points
+            .iter()
+            .map(|&(row, col)| (rows.index(row).unwrap(), cols.index(col).unwrap()))
+            .collect();
+
+        Self { rows, cols, points }
+    }
+
+    pub fn rows(&self) -> &Axis {
+        &self.rows
+    }
+
+    pub fn cols(&self) -> &Axis {
+        &self.cols
+    }
+
+    /// Points as `(row, col)` compressed indices, in input order.
+    pub fn compressed(&self) -> &[(usize, usize)] {
+        &self.points
+    }
+
+    /// Maps compressed indices back to the original point.
+    pub fn original(&self, (row, col): (usize, usize)) -> Point {
+        (self.rows.value(row), self.cols.value(col))
+    }
+
+    /// Points with empty coordinates weighted, in input order.
+    pub fn weighted(&self) -> impl Iterator<Item = Point> + '_ {
+        self.points
+            .iter()
+            .map(|&(row, col)| (self.rows.position(row), self.cols.position(col)))
+    }
+
+    /// Sum of the Manhattan distances between every pair of weighted points.
+    pub fn pairwise_manhattan_sum(&self) -> i64 {
+        pairwise_manhattan_sum(self.weighted())
+    }
+}
+
+/// Sum of `|a - b|` over every unordered pair of values, in O(n log n).
+pub fn pairwise_distance_sum(values: impl IntoIterator<Item = i64>) -> i64 {
+    let mut values: Vec<i64> = values.into_iter().collect();
+    values.sort_unstable();
+
+    // once sorted, the i-th value is greater than the i values before it, whose sum is `prefix`.
+    let mut prefix = 0;
+    let mut total = 0;
+    for (i, &value) in values.iter().enumerate() {
+        total += value * i as i64 - prefix;
+        prefix += value;
+    }
+
+    total
+}
+
+/// Sum of the Manhattan distances between every unordered pair of points, in O(n log n).
+pub fn pairwise_manhattan_sum(points: impl IntoIterator<Item = Point>) -> i64 {
+    let (rows, cols): (Vec<i64>, Vec<i64>) = points.into_iter().unzip();
+    pairwise_distance_sum(rows) + pairwise_distance_sum(cols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairwise_distance_sum() {
+        assert_eq!(pairwise_distance_sum([3, 1, 7]), 2 + 6 + 4);
+        assert_eq!(pairwise_distance_sum([5, 5, -5]), 20);
+        assert_eq!(pairwise_distance_sum([]), 0);
+    }
+
+    #[test]
+    fn test_pairwise_manhattan_sum_matches_brute_force() {
+        let points: [Point; 6] = [(0, 3), (1, 7), (2, 0), (4, 6), (5, 1), (-3, 2)];
+        let brute_force: i64 = points
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| points[i + 1..].iter().map(move |b| (a, b)))
+            .map(|(a, b)| (a.0 - b.0).abs() + (a.1 - b.1).abs())
+            .sum();
+        assert_eq!(pairwise_manhattan_sum(points), brute_force);
+    }
+
+    #[test]
+    fn test_axis() {
+        let axis = Axis::new([7, 2, 4, 2], 10);
+        assert_eq!(axis.values(), &[2, 4, 7]);
+        assert_eq!(axis.len(), 3);
+        assert_eq!(axis.index(4), Some(1));
+        assert_eq!(axis.index(5), None);
+        assert_eq!(axis.value(2), 7);
+        assert_eq!(axis.width(1), 3);
+        // 0 and 1 are empty, then one empty between 2 and 4, two between 4 and 7.
+        assert_eq!(axis.position(0), 20);
+        assert_eq!(axis.position(1), 31);
+        assert_eq!(axis.position(2), 52);
+    }
+
+    #[test]
+    fn test_compressed_points() {
+        let points = [(0, 3), (4, 0), (4, 3)];
+        let compressed = CompressedPoints::new(&points, 1, 2);
+        assert_eq!(compressed.compressed(), &[(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(compressed.original((1, 0)), (4, 0));
+        assert_eq!(
+            compressed.weighted().collect::<Vec<_>>(),
+            vec![(0, 5), (4, 0), (4, 5)]
+        );
+        assert_eq!(compressed.pairwise_manhattan_sum(), 9 + 4 + 5);
+    }
+}
@@ -1,11 +1,15 @@
 use grid::*;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Range};
 use std::str::FromStr;
 
+pub use symmetry::*;
 
-#[derive(Clone, Copy, PartialEq)]
+mod symmetry;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
     Up,
     Down,
@@ -25,6 +29,31 @@ impl Direction {
     }
 }
 
+/// A maximal run of cells along a row or a column, see [`CustomGrid::horizontal_spans`] and
+/// [`CustomGrid::vertical_spans`]. The range across the run always holds a single index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span<V> {
+    pub rows: Range<usize>,
+    pub cols: Range<usize>,
+    pub value: V,
+}
+
+impl<V> Span<V> {
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rows
+            .clone()
+            .flat_map(|row| self.cols.clone().map(move |col| (row, col)))
+    }
+
+    /// Cells touching the span, diagonals included, clipped to the grid.
+    pub fn border<'a, T: Eq + Hash>(
+        &self,
+        grid: &'a CustomGrid<T>,
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        grid.iter_border(self.rows.clone(), self.cols.clone())
+    }
+}
+
 #[derive(Eq, PartialEq, Clone)]
 #[repr(transparent)]
 pub struct CustomGrid<T: Eq + Hash>(Grid<T>);
@@ -239,6 +268,84 @@ impl<T: Eq + Hash> CustomGrid<T> {
     }
 }
 
+impl<T: Clone + Eq + Hash> CustomGrid<T> {
+    /// Maximal runs of cells matching `predicate` along each row, from top-left. The span value
+    /// is collected from its cells, e.g. a `String` for a `CustomGrid<char>`.
+    pub fn horizontal_spans<V: FromIterator<T>>(
+        &self,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Vec<Span<V>> {
+        (0..self.rows())
+            .flat_map(|row| {
+                runs(self.iter_row(row), &predicate).into_iter().map(move |(cols, value)| Span {
+                    rows: row..row + 1,
+                    cols,
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// Maximal runs of cells matching `predicate` along each column, from top-left.
+    pub fn vertical_spans<V: FromIterator<T>>(
+        &self,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Vec<Span<V>> {
+        (0..self.cols())
+            .flat_map(|col| {
+                runs(self.iter_col(col), &predicate).into_iter().map(move |(rows, value)| Span {
+                    rows,
+                    cols: col..col + 1,
+                    value,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<T: Eq + Hash> CustomGrid<T> {
+    /// Cells around the `rows` × `cols` rectangle, diagonals included, clipped to the grid.
+    pub fn iter_border(
+        &self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let outer_rows = rows.start.saturating_sub(1)..(rows.end + 1).min(self.rows());
+        let outer_cols = cols.start.saturating_sub(1)..(cols.end + 1).min(self.cols());
+
+        outer_rows
+            .flat_map(move |row| outer_cols.clone().map(move |col| (row, col)))
+            .filter(move |(row, col)| !rows.contains(row) || !cols.contains(col))
+            .map(|(row, col)| ((row, col), self.0.get(row, col).unwrap()))
+    }
+}
+
+/// Splits a line into maximal runs of cells matching `predicate`, with their index range.
+fn runs<'a, T: Clone + 'a, V: FromIterator<T>>(
+    line: impl Iterator<Item = &'a T>,
+    predicate: &impl Fn(&T) -> bool,
+) -> Vec<(Range<usize>, V)> {
+    let cells: Vec<&T> = line.collect();
+    let mut runs = vec![];
+    let mut index = 0;
+
+    while index < cells.len() {
+        if !predicate(cells[index]) {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < cells.len() && predicate(cells[index]) {
+            index += 1;
+        }
+        let value = cells[start..index].iter().map(|&cell| cell.clone()).collect();
+        runs.push((start..index, value));
+    }
+
+    runs
+}
+
 impl<T: Display + Eq + Hash> CustomGrid<T> {
     pub fn print(grid: &CustomGrid<T>) {
         for row in 0..grid.0.rows() {
@@ -262,3 +369,51 @@ pub fn input_to_grid<T: FromStr + Eq + Hash>(input: &str) -> Result<CustomGrid<T
 
     Ok(CustomGrid(Grid::from_vec(grid_data?, cols)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> CustomGrid<char> {
+        input_to_grid("467..\n...*.\n..35.\n#...6\n").unwrap()
+    }
+
+    #[test]
+    fn test_horizontal_spans() {
+        let spans: Vec<Span<String>> = grid().horizontal_spans(char::is_ascii_digit);
+        assert_eq!(
+            spans,
+            vec![
+                Span { rows: 0..1, cols: 0..3, value: "467".to_string() },
+                Span { rows: 2..3, cols: 2..4, value: "35".to_string() },
+                Span { rows: 3..4, cols: 4..5, value: "6".to_string() },
+            ]
+        );
+        assert_eq!(spans[1].cells().collect::<Vec<_>>(), vec![(2, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn test_vertical_spans() {
+        let spans: Vec<Span<String>> = grid().vertical_spans(|c| *c != '.');
+        assert_eq!(spans[0], Span { rows: 0..1, cols: 0..1, value: "4".to_string() });
+        assert_eq!(spans[5], Span { rows: 1..3, cols: 3..4, value: "*5".to_string() });
+        assert_eq!(spans[5].cells().collect::<Vec<_>>(), vec![(1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn test_span_border() {
+        let grid = grid();
+        let spans: Vec<Span<String>> = grid.horizontal_spans(char::is_ascii_digit);
+
+        // clipped at the top-left corner
+        let border: Vec<_> = spans[0].border(&grid).map(|(pos, _)| pos).collect();
+        assert_eq!(border, vec![(0, 3), (1, 0), (1, 1), (1, 2), (1, 3)]);
+
+        let symbols: Vec<_> = spans
+            .iter()
+            .filter(|span| span.border(&grid).any(|(_, c)| *c != '.' && !c.is_ascii_digit()))
+            .map(|span| span.value.as_str())
+            .collect();
+        assert_eq!(symbols, vec!["467", "35"]);
+    }
+}
@@ -0,0 +1,170 @@
+//! Reflection and rotation symmetry of grids.
+//!
+//! Cells are reduced to bits by a predicate, and each row and column is packed into a bitmask so
+//! that comparing two lines is a handful of xor and popcount operations.
+use std::hash::Hash;
+
+use super::CustomGrid;
+
+/// A mirror line through a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Reflection {
+    /// Mirror between row `n - 1` and row `n`, `n` being the number of rows above it.
+    Horizontal(usize),
+    /// Mirror between column `n - 1` and column `n`, `n` being the number of columns left of it.
+    Vertical(usize),
+}
+
+type BitMask = Vec<u64>;
+
+fn bitmask<'a, T: 'a>(line: impl Iterator<Item = &'a T>, is_set: &impl Fn(&T) -> bool) -> BitMask {
+    let mut mask = vec![];
+    for (i, cell) in line.enumerate() {
+        if i % 64 == 0 {
+            mask.push(0);
+        }
+        if is_set(cell) {
+            *mask.last_mut().unwrap() |= 1 << (i % 64);
+        }
+    }
+    mask
+}
+
+fn mismatches(line: &BitMask, other: &BitMask) -> u32 {
+    line.iter()
+        .zip(other)
+        .map(|(a, b)| (a ^ b).count_ones())
+        .sum()
+}
+
+/// Positions `n` such that mirroring the lines around `n` mismatches exactly `expected` cells.
+fn mirror_positions(lines: &[BitMask], expected: u32) -> impl Iterator<Item = usize> + '_ {
+    (1..lines.len()).filter(move |&n| {
+        let mut total = 0;
+        for (before, after) in lines[..n].iter().rev().zip(&lines[n..]) {
+            total += mismatches(before, after);
+            if total > expected {
+                return false;
+            }
+        }
+        total == expected
+    })
+}
+
+impl<T: Eq + Hash> CustomGrid<T> {
+    /// Every horizontal and vertical mirror line with exactly `mismatches` differing cells on
+    /// the overlapping part, cells being compared through `is_set`. Lines past a grid edge are
+    /// ignored. Horizontal reflections come first, from the top.
+    pub fn reflections(&self, is_set: impl Fn(&T) -> bool, mismatches: u32) -> Vec<Reflection> {
+        let rows: Vec<BitMask> = (0..self.rows())
+            .map(|row| bitmask(self.iter_row(row), &is_set))
+            .collect();
+        let cols: Vec<BitMask> = (0..self.cols())
+            .map(|col| bitmask(self.iter_col(col), &is_set))
+            .collect();
+
+        mirror_positions(&rows, mismatches)
+            .map(Reflection::Horizontal)
+            .chain(mirror_positions(&cols, mismatches).map(Reflection::Vertical))
+            .collect()
+    }
+
+    /// Order of the rotational symmetry of the grid: `4` if it is unchanged by a quarter turn,
+    /// `2` by a half turn only, `1` otherwise.
+    pub fn rotational_symmetry(&self, is_set: impl Fn(&T) -> bool) -> usize {
+        let (rows, cols) = (self.rows(), self.cols());
+        let bit = |row: usize, col: usize| is_set(self.get(row, col).unwrap());
+        let cells = || (0..rows).flat_map(move |row| (0..cols).map(move |col| (row, col)));
+
+        let half_turn =
+            cells().all(|(row, col)| bit(row, col) == bit(rows - 1 - row, cols - 1 - col));
+        if !half_turn {
+            return 1;
+        }
+
+        let quarter_turn =
+            rows == cols && cells().all(|(row, col)| bit(row, col) == bit(col, rows - 1 - row));
+        if quarter_turn {
+            4
+        } else {
+            2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_grid::input_to_grid;
+
+    fn grid(input: &str) -> CustomGrid<char> {
+        input_to_grid(input).unwrap()
+    }
+
+    fn is_rock(c: &char) -> bool {
+        *c == '#'
+    }
+
+    #[test]
+    fn test_exact_reflections() {
+        let vertical =
+            grid("#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n..##..##.\n#.#.##.#.\n");
+        assert_eq!(
+            vertical.reflections(is_rock, 0),
+            vec![Reflection::Vertical(5)]
+        );
+
+        let horizontal =
+            grid("#...##..#\n#....#..#\n..##..###\n#####.##.\n#####.##.\n..##..###\n#....#..#\n");
+        assert_eq!(
+            horizontal.reflections(is_rock, 0),
+            vec![Reflection::Horizontal(4)]
+        );
+    }
+
+    #[test]
+    fn test_smudged_reflections() {
+        let vertical =
+            grid("#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n..##..##.\n#.#.##.#.\n");
+        assert_eq!(
+            vertical.reflections(is_rock, 1),
+            vec![Reflection::Horizontal(3)]
+        );
+
+        let horizontal =
+            grid("#...##..#\n#....#..#\n..##..###\n#####.##.\n#####.##.\n..##..###\n#....#..#\n");
+        assert_eq!(
+            horizontal.reflections(is_rock, 1),
+            vec![Reflection::Horizontal(1)]
+        );
+    }
+
+    #[test]
+    fn test_wide_grid() {
+        // 70 columns, mirrored around the middle, across the 64 bits boundary.
+        let half: String = (0..35)
+            .map(|i| {
+                if [0, 2, 3, 9, 17, 30].contains(&i) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let row: String = half.chars().chain(half.chars().rev()).collect();
+        let wide = grid(&format!("{row}\n{row}\n"));
+        assert_eq!(
+            wide.reflections(is_rock, 0),
+            vec![Reflection::Horizontal(1), Reflection::Vertical(35)]
+        );
+    }
+
+    #[test]
+    fn test_rotational_symmetry() {
+        assert_eq!(grid(".#.\n###\n.#.\n").rotational_symmetry(is_rock), 4);
+        assert_eq!(grid("#..\n...\n..#\n").rotational_symmetry(is_rock), 2);
+        assert_eq!(grid("##.\n...\n").rotational_symmetry(is_rock), 1);
+        assert_eq!(grid("#.\n.#\n.#\n#.\n").rotational_symmetry(is_rock), 1);
+        assert_eq!(grid("#..\n..#\n").rotational_symmetry(is_rock), 2);
+    }
+}
@@ -0,0 +1,205 @@
+//! Lattice polygon geometry: shoelace area, boundary counting, Pick's theorem and
+//! point-in-polygon tests.
+//!
+//! Points are `(row, col)` pairs, matching [`CustomGrid`](crate::custom_grid::CustomGrid) indexing.
+use num::integer::gcd;
+
+use crate::custom_grid::Direction;
+
+pub type Point = (i64, i64);
+
+/// Where a point lies relative to a [`Polygon`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointLocation {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// A simple closed polygon with integer vertices. The last vertex connects back to the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn from_vertices(vertices: Vec<Point>) -> Self {
+        let mut vertices = vertices;
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Self { vertices }
+    }
+
+    /// Builds a polygon by walking `(direction, length)` steps from `(0, 0)`.
+    pub fn from_steps(steps: impl IntoIterator<Item = (Direction, i64)>) -> Self {
+        let mut position = (0, 0);
+        let mut vertices = vec![position];
+
+        for (direction, length) in steps {
+            let (row, col) = position;
+            position = match direction {
+                Direction::Up => (row - length, col),
+                Direction::Down => (row + length, col),
+                Direction::Right => (row, col + length),
+                Direction::Left => (row, col - length),
+            };
+            vertices.push(position);
+        }
+
+        Self::from_vertices(vertices)
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    /// Twice the signed area, computed with the shoelace formula. Kept doubled so it stays an
+    /// integer for any lattice polygon.
+    pub fn double_signed_area(&self) -> i64 {
+        self.edges()
+            .map(|((row, col), (next_row, next_col))| row * next_col - next_row * col)
+            .sum()
+    }
+
+    pub fn area(&self) -> f64 {
+        self.double_signed_area().abs() as f64 / 2.0
+    }
+
+    /// Number of lattice points lying on the polygon edges.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|((row, col), (next_row, next_col))| gcd(next_row - row, next_col - col))
+            .sum()
+    }
+
+    /// Number of lattice points strictly inside the polygon, from Pick's theorem:
+    /// `A = I + B / 2 - 1`.
+    pub fn interior_points(&self) -> i64 {
+        (self.double_signed_area().abs() - self.boundary_points() + 2) / 2
+    }
+
+    /// Locates a point with a horizontal ray cast, edges are checked first so that boundary
+    /// points are reported as such.
+    pub fn locate(&self, (row, col): Point) -> PointLocation {
+        let mut inside = false;
+
+        for ((a_row, a_col), (b_row, b_col)) in self.edges() {
+            let (d_row, d_col) = (b_row - a_row, b_col - a_col);
+            let cross = (row - a_row) * d_col - (col - a_col) * d_row;
+
+            if cross == 0
+                && row >= a_row.min(b_row)
+                && row <= a_row.max(b_row)
+                && col >= a_col.min(b_col)
+                && col <= a_col.max(b_col)
+            {
+                return PointLocation::Boundary;
+            }
+
+            // the edge spans the point's row (half-open, so shared vertices count once) and
+            // crosses it to the right of the point.
+            if (a_row > row) != (b_row > row) && (cross > 0) == (d_row > 0) {
+                inside = !inside;
+            }
+        }
+
+        if inside {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+
+    /// Whether the point lies strictly inside the polygon.
+    pub fn contains(&self, point: Point) -> bool {
+        self.locate(point) == PointLocation::Inside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_grid::Direction::{Down, Left, Right, Up};
+
+    fn square() -> Polygon {
+        Polygon::from_steps([(Right, 4), (Down, 4), (Left, 4), (Up, 4)])
+    }
+
+    #[test]
+    fn test_from_steps() {
+        assert_eq!(square().vertices(), &[(0, 0), (0, 4), (4, 4), (4, 0)]);
+    }
+
+    #[test]
+    fn test_area_and_points() {
+        let polygon = square();
+        assert_eq!(polygon.double_signed_area(), -32);
+        assert_eq!(polygon.area(), 16.0);
+        assert_eq!(polygon.boundary_points(), 16);
+        assert_eq!(polygon.interior_points(), 9);
+    }
+
+    #[test]
+    fn test_triangle() {
+        let polygon = Polygon::from_vertices(vec![(0, 0), (0, 4), (3, 0), (0, 0)]);
+        assert_eq!(polygon.vertices().len(), 3);
+        assert_eq!(polygon.area(), 6.0);
+        assert_eq!(polygon.boundary_points(), 8);
+        assert_eq!(polygon.interior_points(), 3);
+    }
+
+    #[test]
+    fn test_locate() {
+        // U shape, open at the top between columns 1 and 3.
+        let polygon = Polygon::from_vertices(vec![
+            (0, 0),
+            (0, 1),
+            (3, 1),
+            (3, 3),
+            (0, 3),
+            (0, 4),
+            (4, 4),
+            (4, 0),
+        ]);
+
+        assert_eq!(polygon.locate((2, 2)), PointLocation::Outside);
+        assert_eq!(polygon.locate((0, 2)), PointLocation::Outside);
+        assert_eq!(polygon.locate((3, 2)), PointLocation::Boundary);
+        assert_eq!(polygon.locate((0, 0)), PointLocation::Boundary);
+        assert_eq!(polygon.locate((2, 0)), PointLocation::Boundary);
+        assert_eq!(polygon.locate((5, 2)), PointLocation::Outside);
+        assert!(!polygon.contains((1, 2)));
+        assert!(!polygon.contains((4, 2)));
+        assert_eq!(square().locate((2, 2)), PointLocation::Inside);
+        assert_eq!(square().locate((4, 2)), PointLocation::Boundary);
+    }
+
+    #[test]
+    fn test_locate_matches_pick() {
+        let polygon = Polygon::from_vertices(vec![
+            (0, 0),
+            (0, 6),
+            (5, 6),
+            (5, 3),
+            (2, 3),
+            (2, 1),
+            (5, 1),
+            (5, 0),
+        ]);
+
+        let inside = (-1..=7)
+            .flat_map(|row| (-1..=7).map(move |col| (row, col)))
+            .filter(|&point| polygon.contains(point))
+            .count() as i64;
+
+        assert_eq!(inside, polygon.interior_points());
+    }
+}
@@ -0,0 +1,224 @@
+//! Graphviz DOT export for puzzle graphs.
+//!
+//! Days feed nodes and labelled edges into a [`Graph`], optionally tagging nodes with a kind
+//! (e.g. `flip-flop` or `conjunction`) that is styled once for the whole graph. The result is
+//! written to `data/visuals/DD/<name>.dot` and can be rendered with `dot -Tsvg`.
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use crate::template::Day;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    id: String,
+    kind: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Edge {
+    from: String,
+    to: String,
+    label: Option<String>,
+}
+
+/// A directed or undirected graph that renders to the DOT language.
+#[derive(Clone, Debug)]
+pub struct Graph {
+    name: String,
+    directed: bool,
+    styles: Vec<(String, Vec<(String, String)>)>,
+    nodes: Vec<Node>,
+    node_index: HashMap<String, usize>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub fn directed(name: &str) -> Self {
+        Self::new(name, true)
+    }
+
+    pub fn undirected(name: &str) -> Self {
+        Self::new(name, false)
+    }
+
+    fn new(name: &str, directed: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            directed,
+            styles: vec![],
+            nodes: vec![],
+            node_index: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    /// Sets the DOT attributes (e.g. `("shape", "box")`) applied to every node of `kind`.
+    pub fn style(&mut self, kind: &str, attributes: &[(&str, &str)]) -> &mut Self {
+        let attributes = attributes
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        match self.styles.iter_mut().find(|(k, _)| k == kind) {
+            Some((_, existing)) => *existing = attributes,
+            None => self.styles.push((kind.to_string(), attributes)),
+        }
+        self
+    }
+
+    /// Adds a node, or updates the kind of an already known node.
+    pub fn node(&mut self, id: &str, kind: Option<&str>) -> &mut Self {
+        let kind = kind.map(str::to_string);
+        match self.node_index.get(id) {
+            Some(&i) => {
+                if kind.is_some() {
+                    self.nodes[i].kind = kind;
+                }
+            }
+            None => {
+                self.node_index.insert(id.to_string(), self.nodes.len());
+                self.nodes.push(Node {
+                    id: id.to_string(),
+                    kind,
+                });
+            }
+        }
+        self
+    }
+
+    /// Adds an edge. Unknown endpoints are added as nodes without a kind.
+    pub fn edge(&mut self, from: &str, to: &str, label: Option<&str>) -> &mut Self {
+        self.node(from, None);
+        self.node(to, None);
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: label.map(str::to_string),
+        });
+        self
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Writes the graph to `data/visuals/<day>/<file_name>.dot` and returns the written path.
+    pub fn write(&self, day: Day, file_name: &str) -> io::Result<PathBuf> {
+        let dir = env::current_dir()?
+            .join("data")
+            .join("visuals")
+            .join(day.to_string());
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("{file_name}.dot"));
+        fs::write(&path, self.to_string())?;
+        Ok(path)
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn format_attributes(attributes: &[(String, String)]) -> String {
+    attributes
+        .iter()
+        .map(|(key, value)| format!("{key}={}", quote(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Display for Graph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        writeln!(f, "{keyword} {} {{", quote(&self.name))?;
+
+        for node in &self.nodes {
+            let style = node
+                .kind
+                .as_ref()
+                .and_then(|kind| self.styles.iter().find(|(k, _)| k == kind))
+                .map(|(_, attributes)| format_attributes(attributes))
+                .unwrap_or_default();
+
+            if style.is_empty() {
+                writeln!(f, "    {};", quote(&node.id))?;
+            } else {
+                writeln!(f, "    {} [{style}];", quote(&node.id))?;
+            }
+        }
+
+        for edge in &self.edges {
+            write!(f, "    {} {arrow} {}", quote(&edge.from), quote(&edge.to))?;
+            match &edge.label {
+                Some(label) => writeln!(f, " [label={}];", quote(label))?,
+                None => writeln!(f, ";")?,
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+
+    #[test]
+    fn renders_styled_nodes_and_labelled_edges() {
+        let mut graph = Graph::directed("modules");
+        graph
+            .style("flip-flop", &[("shape", "box")])
+            .node("a", Some("flip-flop"))
+            .edge("a", "b", Some("low"))
+            .edge("b", "a", None);
+
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(
+            graph.to_string(),
+            [
+                "digraph \"modules\" {",
+                "    \"a\" [shape=\"box\"];",
+                "    \"b\";",
+                "    \"a\" -> \"b\" [label=\"low\"];",
+                "    \"b\" -> \"a\";",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn escapes_quotes() {
+        let mut graph = Graph::undirected("g");
+        graph.edge("say \"hi\"", "x", None);
+
+        assert!(graph
+            .to_string()
+            .contains("\"say \\\"hi\\\"\" -- \"x\";"));
+    }
+
+    #[test]
+    fn keeps_kind_when_node_is_re_added_by_edge() {
+        let mut graph = Graph::directed("g");
+        graph
+            .style("k", &[("color", "red")])
+            .node("a", Some("k"))
+            .edge("a", "a", None);
+
+        assert!(graph.to_string().contains("\"a\" [color=\"red\"];"));
+    }
+}
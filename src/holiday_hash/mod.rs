@@ -0,0 +1,182 @@
+//! The HASH algorithm of day 15 as a std [`Hasher`], and the HASHMAP lens boxes built on it.
+//!
+//! HASH folds every byte with `(state + byte) * 17 % 256`. It is a poor general purpose hash, only
+//! 256 values, which makes [`BuildHolidayHasher`] handy to exercise collision heavy code paths of
+//! std collections.
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Runs the HASH algorithm on `bytes`.
+pub fn holiday_hash(bytes: &[u8]) -> u8 {
+    let mut hasher = HolidayHasher::default();
+    hasher.write(bytes);
+    hasher.state
+}
+
+/// [`Hasher`] running the HASH algorithm over everything written to it.
+///
+/// Note that hashing a `str` through [`Hash`] also writes a `0xff` terminator, so the result
+/// differs from [`holiday_hash`] on the same text.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HolidayHasher {
+    state: u8,
+}
+
+impl Hasher for HolidayHasher {
+    fn finish(&self) -> u64 {
+        self.state as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.state = bytes.iter().fold(self.state, |state, &byte| {
+            state.wrapping_add(byte).wrapping_mul(17)
+        });
+    }
+}
+
+pub type BuildHolidayHasher = BuildHasherDefault<HolidayHasher>;
+
+/// The HASHMAP of day 15: 256 boxes of lenses, each box keeping its lenses in insertion order.
+///
+/// Every box is a list of `(label, lens)`, so insert, replace, remove and reading a box only
+/// touch the lenses of that box.
+#[derive(Clone, Debug)]
+pub struct LensMap<K, V> {
+    boxes: Vec<Vec<(K, V)>>,
+    len: usize,
+}
+
+impl<K: AsRef<[u8]> + Eq, V> LensMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            boxes: (0..256).map(|_| Vec::new()).collect(),
+            len: 0,
+        }
+    }
+
+    fn lens_box(&self, label: &K) -> &Vec<(K, V)> {
+        &self.boxes[holiday_hash(label.as_ref()) as usize]
+    }
+
+    fn lens_box_mut(&mut self, label: &K) -> &mut Vec<(K, V)> {
+        &mut self.boxes[holiday_hash(label.as_ref()) as usize]
+    }
+
+    /// Puts a lens in its box, replacing the lens with the same label in place if there is one.
+    pub fn insert(&mut self, label: K, value: V) -> Option<V> {
+        let lens_box = self.lens_box_mut(&label);
+        if let Some((_, lens)) = lens_box.iter_mut().find(|(own, _)| *own == label) {
+            return Some(std::mem::replace(lens, value));
+        }
+
+        lens_box.push((label, value));
+        self.len += 1;
+        None
+    }
+
+    /// Takes the lens with the given label out of its box.
+    pub fn remove(&mut self, label: &K) -> Option<V> {
+        let lens_box = self.lens_box_mut(label);
+        let index = lens_box.iter().position(|(own, _)| own == label)?;
+        let (_, lens) = lens_box.remove(index);
+
+        self.len -= 1;
+        Some(lens)
+    }
+
+    pub fn get(&self, label: &K) -> Option<&V> {
+        self.lens_box(label)
+            .iter()
+            .find(|(own, _)| own == label)
+            .map(|(_, lens)| lens)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Lenses of one box, front to back.
+    pub fn bucket(&self, bucket: u8) -> impl Iterator<Item = (&K, &V)> {
+        self.boxes[bucket as usize]
+            .iter()
+            .map(|(label, lens)| (label, lens))
+    }
+
+    /// Sum over every lens of `(box + 1) * (slot in box + 1) * focal length`.
+    pub fn focusing_power(&self) -> u64
+    where
+        V: Copy + Into<u64>,
+    {
+        self.boxes
+            .iter()
+            .zip(1..)
+            .flat_map(|(lens_box, box_number)| {
+                lens_box
+                    .iter()
+                    .zip(1..)
+                    .map(move |((_, lens), slot)| box_number * slot * (*lens).into())
+            })
+            .sum()
+    }
+}
+
+impl<K: AsRef<[u8]> + Eq, V> Default for LensMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn test_holiday_hash() {
+        assert_eq!(holiday_hash(b"HASH"), 52);
+        assert_eq!(holiday_hash(b"rn"), 0);
+        assert_eq!(holiday_hash(b"qp"), 1);
+        assert_eq!(holiday_hash(b""), 0);
+    }
+
+    #[test]
+    fn test_hasher() {
+        let mut hasher = HolidayHasher::default();
+        hasher.write(b"HA");
+        hasher.write(b"SH");
+        assert_eq!(hasher.finish(), 52);
+
+        // `str` keys are terminated, but still usable with std collections.
+        let set: HashSet<&str, BuildHolidayHasher> =
+            ["rn", "cm", "qp", "pc", "ot", "ab"].into_iter().collect();
+        assert!(set.contains("qp"));
+        assert!(!set.contains("zz"));
+        assert!(BuildHolidayHasher::default().hash_one("rn") < 256);
+    }
+
+    #[test]
+    fn test_lens_map() {
+        let mut lenses = LensMap::new();
+        assert_eq!(lenses.insert("rn", 1u8), None);
+        lenses.insert("cm", 2);
+        lenses.insert("qp", 3);
+        lenses.insert("pc", 4);
+        assert_eq!(lenses.insert("rn", 5), Some(1));
+        assert_eq!(lenses.remove(&"cm"), Some(2));
+        lenses.insert("cm", 6);
+
+        assert_eq!(lenses.len(), 4);
+        assert_eq!(lenses.get(&"rn"), Some(&5));
+        assert_eq!(
+            lenses.bucket(0).collect::<Vec<_>>(),
+            vec![(&"rn", &5), (&"cm", &6)]
+        );
+        assert_eq!(lenses.bucket(2).count(), 0);
+        assert_eq!(lenses.focusing_power(), 5 + 2 * 6 + 2 * 3 + 4 * 4);
+        assert_eq!(lenses.remove(&"zz"), None);
+    }
+}
@@ -1,3 +1,12 @@
+pub mod beam;
+pub mod cards;
+pub mod compression;
 pub mod custom_grid;
+pub mod geometry;
+pub mod graphviz;
+pub mod holiday_hash;
+pub mod math;
+pub mod nonogram;
+pub mod string_search;
 pub mod template;
 // Use this file to add helper functions and additional modules.
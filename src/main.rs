@@ -1,4 +1,4 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, matrix, read, scaffold, solve, time};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,8 +7,10 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::limits::Limits;
+    use advent_of_code::template::{Day, DaySet, Input};
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
@@ -25,35 +27,84 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            profile_cpu: bool,
             submit: Option<u8>,
+            watch: bool,
+            input: Input,
         },
         All {
+            days: DaySet,
+            release: bool,
+            limits: Limits,
+            jobs: usize,
+        },
+        Matrix {
+            days: DaySet,
             release: bool,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySet>,
             store: bool,
+            limits: Limits,
+        },
+        TimeHistory {
+            day: Day,
+            commits: Option<String>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Limits of `data/limits.json`, with defaults replaced by `--timeout <secs>` and
+    /// `--memory-limit <MiB>`.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+        let memory_mb: Option<u64> = args.opt_value_from_str("--memory-limit")?;
+
+        Ok(Limits::read_from_file()
+            .with_timeout(timeout.map(Duration::from_secs_f64))
+            .with_memory_bytes(memory_mb.map(|mb| mb * 1024 * 1024)))
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+            Some("all") => {
+                let release = args.contains("--release");
+                let limits = parse_limits(&mut args)?;
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+
+                AppArguments::All {
+                    days: args.opt_free_from_str()?.unwrap_or_else(DaySet::all),
+                    release,
+                    limits,
+                    jobs,
+                }
+            }
+            Some("matrix") => {
+                let release = args.contains("--release");
+
+                AppArguments::Matrix {
+                    days: args.opt_free_from_str()?.unwrap_or_else(DaySet::all),
+                    release,
+                }
+            }
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory {
+                day: args.free_from_str()?,
+                commits: args.opt_free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let limits = parse_limits(&mut args)?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: args.opt_free_from_str()?,
                     store,
+                    limits,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -66,12 +117,32 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let profile_cpu = args.contains("--profile-cpu");
+                let watch = args.contains("--watch");
+                let path: Option<String> = args.opt_value_from_str("--input")?;
+                // the part of `--example [N]` is whatever free argument is left after the day.
+                let example = args
+                    .contains("--example")
+                    .then(|| args.opt_free_from_str())
+                    .transpose()?;
+
+                let input = Input::new(path.as_deref(), example)?;
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    profile_cpu,
+                    submit,
+                    watch,
+                    input,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -100,8 +171,22 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                days,
+                release,
+                limits,
+                jobs,
+            } => all::handle(&days, release, &limits, jobs),
+            AppArguments::Matrix { days, release } => matrix::handle(&days, release),
+            AppArguments::Time {
+                days,
+                all,
+                store,
+                limits,
+            } => time::handle(days, all, store, &limits),
+            AppArguments::TimeHistory { day, commits } => {
+                time::handle_history(day, commits.as_deref());
+            }
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day, download } => {
@@ -114,8 +199,11 @@ fn main() {
                 day,
                 release,
                 dhat,
+                profile_cpu,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+                input,
+            } => solve::handle(day, release, dhat, profile_cpu, submit, watch, &input),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
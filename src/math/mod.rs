@@ -0,0 +1,7 @@
+pub use number_theory::*;
+pub use polynomial::*;
+pub use quadratic::*;
+
+mod number_theory;
+mod polynomial;
+mod quadratic;
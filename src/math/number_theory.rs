@@ -0,0 +1,170 @@
+//! Number theory helpers: extended gcd, modular arithmetic and the chinese remainder theorem.
+//!
+//! Intermediate values are computed on `i128` so that products of two `i64` never overflow.
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the non-negative gcd of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+
+    (old_r as i64, old_x as i64, old_y as i64)
+}
+
+/// Returns the inverse of `a` modulo `m` in `0..m`, or `None` if `a` and `m` are not coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m))
+}
+
+/// Computes `base^exp mod modulus` by repeated squaring.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "modulus must be positive");
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair.
+///
+/// Moduli don't need to be coprime. Returns `(x, lcm)` with `x` in `0..lcm`, or `None` if the
+/// congruences contradict each other.
+///
+/// # Panics
+/// If a modulus is not positive or if the lcm of the moduli does not fit in an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+
+    for &(residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus must be positive");
+        let (residue, modulus) = ((residue as i128).rem_euclid(modulus as i128), modulus as i128);
+
+        // x + m * k ≡ residue (mod modulus)  <=>  m * k ≡ residue - x (mod modulus)
+        let (g, p, _) = extended_gcd(m as i64, modulus as i64);
+        let (g, p) = (g as i128, p as i128);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = (diff / g % step * p).rem_euclid(step);
+        x += m * k;
+        m *= step;
+        assert!(m <= i64::MAX as i128, "lcm of moduli overflows i64");
+        x = x.rem_euclid(m);
+    }
+
+    Some((x as i64, m as i64))
+}
+
+/// Finds the first time at which every cycle fires simultaneously.
+///
+/// Each cycle is an `(offset, period)` pair that fires at `offset + k * period` for all `k >= 0`.
+/// A period of `0` means the cycle fires only once, at its offset. Returns `None` if the cycles
+/// never line up.
+pub fn first_common_time(cycles: &[(u64, u64)]) -> Option<u64> {
+    let start = cycles.iter().map(|&(offset, _)| offset).max()?;
+
+    if let Some(&(offset, _)) = cycles.iter().find(|&&(_, period)| period == 0) {
+        return cycles
+            .iter()
+            .all(|&(o, p)| {
+                if p == 0 {
+                    o == offset
+                } else {
+                    offset >= o && (offset - o) % p == 0
+                }
+            })
+            .then_some(offset);
+    }
+
+    let congruences: Vec<_> = cycles
+        .iter()
+        .map(|&(offset, period)| (offset as i64, period as i64))
+        .collect();
+    let (x, m) = crt(&congruences)?;
+    let (x, m) = (x as u64, m as u64);
+
+    if x >= start {
+        Some(x)
+    } else {
+        Some(x + (start - x).div_ceil(m) * m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(0, 5), (5, 0, 1));
+        let (g, x, y) = extended_gcd(-12, 18);
+        assert_eq!(g, 6);
+        assert_eq!(-12 * x + 18 * y, 6);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn test_crt_coprime() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_crt_non_coprime() {
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn test_first_common_time() {
+        // cycles whose offset equals their period reduce to the lcm.
+        assert_eq!(first_common_time(&[(2, 2), (3, 3)]), Some(6));
+        assert_eq!(first_common_time(&[(3, 4), (5, 6)]), Some(11));
+        assert_eq!(first_common_time(&[(1, 4), (2, 6)]), None);
+        assert_eq!(first_common_time(&[(10, 0), (4, 3)]), Some(10));
+        assert_eq!(first_common_time(&[(10, 0), (4, 4)]), None);
+        assert_eq!(first_common_time(&[]), None);
+    }
+}
@@ -0,0 +1,183 @@
+//! Exact polynomial fitting and extrapolation.
+//!
+//! [`extrapolate`] covers the common "sample a few points, then jump far ahead" case through
+//! finite differences on integers. [`Polynomial`] fits arbitrary sample points with Lagrange
+//! interpolation over rationals, for when samples are not evenly spaced.
+use num::rational::Ratio;
+use num::{One, Zero};
+
+pub type Rational = Ratio<i128>;
+
+/// Extrapolates the value at index `x` of the polynomial sequence whose values at `0, 1, 2, ...`
+/// are `samples`. `x` may be negative or far beyond the samples.
+///
+/// The sequence is expanded as a Newton series `Σ Δᵏy₀ · C(x, k)` over its finite difference
+/// table, so integer samples always produce an exact integer result.
+pub fn extrapolate<T: Copy + Into<i128>>(samples: &[T], x: i128) -> i128 {
+    let mut differences: Vec<i128> = samples.iter().map(|&y| y.into()).collect();
+    let mut result = 0;
+    // generalised binomial coefficient C(x, k), valid for negative `x` too.
+    let mut binomial = 1i128;
+
+    for k in 0..differences.len() {
+        result += differences[0] * binomial;
+
+        differences = differences.windows(2).map(|w| w[1] - w[0]).collect();
+        if differences.iter().all(|d| *d == 0) {
+            break;
+        }
+
+        // C(x, k + 1) = C(x, k) * (x - k) / (k + 1), the division is always exact.
+        binomial = binomial * (x - k as i128) / (k as i128 + 1);
+    }
+
+    result
+}
+
+/// A polynomial with rational coefficients, stored from the constant term upwards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<Rational>) -> Self {
+        let mut polynomial = Self { coefficients };
+        polynomial.trim();
+        polynomial
+    }
+
+    /// Fits the unique polynomial of lowest degree through the given `(x, y)` points.
+    ///
+    /// # Panics
+    /// If two points share the same `x`.
+    pub fn interpolate(points: &[(i128, i128)]) -> Self {
+        let mut coefficients = vec![Rational::zero(); points.len()];
+
+        for (i, &(x_i, y_i)) in points.iter().enumerate() {
+            // Lagrange basis polynomial: Π (x - x_j) / (x_i - x_j) for every j != i.
+            let mut basis = vec![Rational::one()];
+            let mut denominator = Rational::one();
+
+            for (j, &(x_j, _)) in points.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                assert_ne!(x_i, x_j, "interpolation points must have distinct x");
+
+                let mut next = vec![Rational::zero(); basis.len() + 1];
+                for (degree, coefficient) in basis.iter().enumerate() {
+                    next[degree + 1] += coefficient;
+                    next[degree] -= coefficient * Rational::from_integer(x_j);
+                }
+                basis = next;
+                denominator *= Rational::from_integer(x_i - x_j);
+            }
+
+            let scale = Rational::from_integer(y_i) / denominator;
+            for (degree, coefficient) in basis.into_iter().enumerate() {
+                coefficients[degree] += coefficient * scale;
+            }
+        }
+
+        Self::new(coefficients)
+    }
+
+    /// Fits the polynomial whose values at `0, 1, 2, ...` are `samples`.
+    pub fn from_samples<T: Copy + Into<i128>>(samples: &[T]) -> Self {
+        let points: Vec<_> = samples
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (x as i128, y.into()))
+            .collect();
+        Self::interpolate(&points)
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    /// Degree of the polynomial. The zero polynomial has degree `0`.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn evaluate(&self, x: i128) -> Rational {
+        let x = Rational::from_integer(x);
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::zero(), |acc, coefficient| acc * x + coefficient)
+    }
+
+    /// Evaluates the polynomial at `x`, returning `None` if the value is not an integer.
+    pub fn evaluate_integer(&self, x: i128) -> Option<i128> {
+        let value = self.evaluate(x);
+        value.is_integer().then(|| value.to_integer())
+    }
+
+    fn trim(&mut self) {
+        while self.coefficients.last().is_some_and(Zero::is_zero) {
+            self.coefficients.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extrapolate_forward_and_backward() {
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15], 6), 18);
+        assert_eq!(extrapolate(&[1, 3, 6, 10, 15, 21], 6), 28);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], 6), 68);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), 5);
+    }
+
+    #[test]
+    fn test_extrapolate_far() {
+        // x² + x + 1
+        let samples: Vec<i64> = (0..3).map(|x| x * x + x + 1).collect();
+        assert_eq!(extrapolate(&samples, 202_300), 202_300 * 202_300 + 202_300 + 1);
+        assert_eq!(extrapolate(&samples, -5), 21);
+    }
+
+    #[test]
+    fn test_extrapolate_constant() {
+        assert_eq!(extrapolate(&[7u32], 1_000), 7);
+        assert_eq!(extrapolate::<i32>(&[], 3), 0);
+    }
+
+    #[test]
+    fn test_interpolate_uneven_points() {
+        // 2x² - 3x + 1
+        let polynomial = Polynomial::interpolate(&[(-2, 15), (1, 0), (5, 36)]);
+        assert_eq!(polynomial.degree(), 2);
+        assert_eq!(
+            polynomial.coefficients(),
+            &[
+                Rational::from_integer(1),
+                Rational::from_integer(-3),
+                Rational::from_integer(2)
+            ]
+        );
+        assert_eq!(polynomial.evaluate_integer(10), Some(171));
+    }
+
+    #[test]
+    fn test_interpolate_rational_coefficients() {
+        // x (x + 1) / 2
+        let polynomial = Polynomial::from_samples(&[0, 1, 3]);
+        assert_eq!(polynomial.coefficients()[2], Rational::new(1, 2));
+        assert_eq!(polynomial.evaluate_integer(100), Some(5050));
+        assert_eq!(polynomial.evaluate(-1), Rational::zero());
+    }
+
+    #[test]
+    fn test_interpolate_lower_degree() {
+        let polynomial = Polynomial::from_samples(&[4, 4, 4, 4]);
+        assert_eq!(polynomial.degree(), 0);
+        assert_eq!(polynomial.evaluate_integer(-50), Some(4));
+    }
+}
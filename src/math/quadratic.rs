@@ -0,0 +1,111 @@
+//! Exact integer solutions of quadratic inequalities.
+//!
+//! Roots are bounded with integer square roots instead of floats, so results stay exact for
+//! coefficients beyond 2^53. Works with any signed integer type, including `BigInt`.
+use std::ops::RangeInclusive;
+
+use num::integer::Roots;
+use num::{Integer, Signed};
+
+/// Returns the integers `x` for which `a·x² + b·x + c < 0`, or `None` if there are none.
+///
+/// # Panics
+/// If `a` is not positive.
+pub fn quadratic_negative_interval<T>(a: T, b: T, c: T) -> Option<RangeInclusive<T>>
+where
+    T: Integer + Signed + Roots + Clone,
+{
+    assert!(a.is_positive(), "leading coefficient must be positive");
+
+    let two = T::one() + T::one();
+    let discriminant = b.clone() * b.clone() - two.clone() * two.clone() * a.clone() * c;
+    if !discriminant.is_positive() {
+        return None;
+    }
+
+    // the roots are (-b ± √D) / 2a, bound them with ⌊-b - √D⌋ and ⌈-b + √D⌉.
+    let root = discriminant.sqrt();
+    let (low, high) = if root.clone() * root.clone() == discriminant {
+        (-b.clone() - root.clone(), -b + root)
+    } else {
+        (-b.clone() - root.clone() - T::one(), -b + root + T::one())
+    };
+
+    let denominator = two * a;
+    let start = low.div_floor(&denominator) + T::one();
+    let end = Integer::div_ceil(&high, &denominator) - T::one();
+
+    (start <= end).then_some(start..=end)
+}
+
+/// Returns the integers `x` for which `a·x² + b·x + c > 0`, or `None` if there are none.
+///
+/// # Panics
+/// If `a` is not negative.
+pub fn quadratic_positive_interval<T>(a: T, b: T, c: T) -> Option<RangeInclusive<T>>
+where
+    T: Integer + Signed + Roots + Clone,
+{
+    assert!(a.is_negative(), "leading coefficient must be negative");
+    quadratic_negative_interval(-a, -b, -c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigInt;
+
+    #[test]
+    fn test_irrational_roots() {
+        // x² - 7x + 9 has roots ~1.70 and ~5.30.
+        assert_eq!(quadratic_negative_interval(1, -7, 9), Some(2..=5));
+    }
+
+    #[test]
+    fn test_integer_roots_are_excluded() {
+        // x² - 5x + 6 = (x - 2)(x - 3) has no integer strictly below zero.
+        assert_eq!(quadratic_negative_interval(1i64, -5, 6), None);
+        // -(x - 10)(x - 20) is positive strictly between the roots.
+        assert_eq!(quadratic_positive_interval(-1i64, 30, -200), Some(11..=19));
+    }
+
+    #[test]
+    fn test_no_real_roots() {
+        assert_eq!(quadratic_negative_interval(1, 0, 1), None);
+        assert_eq!(quadratic_negative_interval(1, 2, 1), None);
+    }
+
+    #[test]
+    fn test_negative_interval() {
+        // 2x² + 7x + 3 has roots -3 and -0.5.
+        assert_eq!(quadratic_negative_interval(2, 7, 3), Some(-2..=-1));
+    }
+
+    #[test]
+    fn test_races() {
+        let holds = quadratic_positive_interval(-1i128, 30, -200).unwrap();
+        assert_eq!(holds, 11..=19);
+
+        let holds = quadratic_positive_interval(-1i128, 71530, -940200).unwrap();
+        assert_eq!(holds.end() - holds.start() + 1, 71503);
+    }
+
+    #[test]
+    fn test_beyond_float_precision() {
+        // roots at 2^60 and 2^60 + 2, only 2^60 + 1 is strictly between them.
+        let r = 1i128 << 60;
+        assert_eq!(
+            quadratic_positive_interval(-1, 2 * r + 2, -(r * (r + 2))),
+            Some(r + 1..=r + 1)
+        );
+
+        let r: BigInt = BigInt::from(1) << 100;
+        let (one, two) = (BigInt::from(1), BigInt::from(2));
+        let b = &two * &r + &two;
+        let c = -(&r * (&r + &two));
+        assert_eq!(
+            quadratic_positive_interval(-one.clone(), b, c),
+            Some(&r + &one..=&r + &one)
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! Nonogram (picross) solver.
+//!
+//! Cells use day 12's alphabet: `#` filled, `.` empty and `?` unknown. A single line is handled
+//! by a prefix DP over `(cells, clues)`, which both counts arrangements and finds the cells that
+//! are forced in every one of them. Grids are solved by line-solving rows and columns until
+//! nothing changes, then backtracking on the first unknown cell.
+use grid::Grid;
+
+use crate::custom_grid::CustomGrid;
+
+pub const FILLED: char = '#';
+pub const EMPTY: char = '.';
+pub const UNKNOWN: char = '?';
+
+/// `table[i][j]` is the number of ways to place the first `j` clues in the first `i` cells, with
+/// every filled cell of that prefix covered. Counts saturate instead of overflowing, which keeps
+/// `> 0` checks meaningful for very long lines.
+fn prefix_table(line: &[char], clues: &[usize]) -> Vec<Vec<u64>> {
+    // empties[i] is the number of `.` in line[..i], to check in O(1) that a block fits.
+    let empties: Vec<usize> = std::iter::once(0)
+        .chain(line.iter().scan(0, |count, &c| {
+            *count += usize::from(c == EMPTY);
+            Some(*count)
+        }))
+        .collect();
+
+    let mut table = vec![vec![0u64; clues.len() + 1]; line.len() + 1];
+    table[0][0] = 1;
+
+    for i in 1..=line.len() {
+        for j in 0..=clues.len() {
+            let mut ways = 0u64;
+
+            // cell i - 1 is empty
+            if line[i - 1] != FILLED {
+                ways = table[i - 1][j];
+            }
+
+            // cell i - 1 ends the j-th block, preceded by an empty cell unless it starts the line
+            if j > 0 {
+                let length = clues[j - 1];
+                if length <= i && empties[i] == empties[i - length] {
+                    let start = i - length;
+                    if start == 0 {
+                        ways = ways.saturating_add(table[0][j - 1]);
+                    } else if line[start - 1] != FILLED {
+                        ways = ways.saturating_add(table[start - 1][j - 1]);
+                    }
+                }
+            }
+
+            table[i][j] = ways;
+        }
+    }
+
+    table
+}
+
+/// Counts the ways to place blocks of `clues` lengths, in order, on a line of `#.?` cells.
+pub fn count_arrangements(line: &[char], clues: &[usize]) -> u64 {
+    prefix_table(line, clues)[line.len()][clues.len()]
+}
+
+/// Returns `line` with every cell that has the same value in all arrangements filled in, or
+/// `None` if there is no arrangement at all.
+pub fn solve_line(line: &[char], clues: &[usize]) -> Option<Vec<char>> {
+    let (n, m) = (line.len(), clues.len());
+    let forward = prefix_table(line, clues);
+    if forward[n][m] == 0 {
+        return None;
+    }
+
+    let reversed_line: Vec<char> = line.iter().rev().copied().collect();
+    let reversed_clues: Vec<usize> = clues.iter().rev().copied().collect();
+    let backward = prefix_table(&reversed_line, &reversed_clues);
+
+    let mut can_be_empty = vec![false; n];
+    // difference array over the cells covered by some feasible block.
+    let mut can_be_filled = vec![0i32; n + 1];
+
+    for (k, &cell) in line.iter().enumerate() {
+        if cell != FILLED {
+            can_be_empty[k] = (0..=m).any(|j| forward[k][j] > 0 && backward[n - k - 1][m - j] > 0);
+        }
+    }
+
+    for (j, &length) in clues.iter().enumerate() {
+        for start in 0..=n.saturating_sub(length) {
+            let end = start + length;
+            if line[start..end].contains(&EMPTY) {
+                continue;
+            }
+
+            let before = if start == 0 {
+                forward[0][j]
+            } else if line[start - 1] == FILLED {
+                0
+            } else {
+                forward[start - 1][j]
+            };
+            let after = if end == n {
+                backward[0][m - j - 1]
+            } else if line[end] == FILLED {
+                0
+            } else {
+                backward[n - end - 1][m - j - 1]
+            };
+
+            if before > 0 && after > 0 {
+                can_be_filled[start] += 1;
+                can_be_filled[end] -= 1;
+            }
+        }
+    }
+
+    let mut covered = 0;
+    let solved = line
+        .iter()
+        .enumerate()
+        .map(|(k, &cell)| {
+            covered += can_be_filled[k];
+            match (covered > 0, can_be_empty[k]) {
+                (true, false) => FILLED,
+                (false, true) => EMPTY,
+                _ => cell,
+            }
+        })
+        .collect();
+
+    Some(solved)
+}
+
+/// Run lengths of the filled cells of a line.
+pub fn clues_of(line: impl IntoIterator<Item = char>) -> Vec<usize> {
+    let mut clues = vec![];
+    let mut run = 0;
+
+    for cell in line.into_iter().chain(std::iter::once(EMPTY)) {
+        if cell == FILLED {
+            run += 1;
+        } else if run > 0 {
+            clues.push(run);
+            run = 0;
+        }
+    }
+
+    clues
+}
+
+/// A nonogram puzzle: one clue list per row and per column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Nonogram {
+    rows: Vec<Vec<usize>>,
+    cols: Vec<Vec<usize>>,
+}
+
+impl Nonogram {
+    pub fn new(rows: Vec<Vec<usize>>, cols: Vec<Vec<usize>>) -> Self {
+        Self { rows, cols }
+    }
+
+    /// Builds the puzzle whose clues describe an already solved grid.
+    pub fn from_solution(grid: &CustomGrid<char>) -> Self {
+        let rows = (0..grid.rows())
+            .map(|row| clues_of(grid.iter_row(row).copied()))
+            .collect();
+        let cols = (0..grid.cols())
+            .map(|col| clues_of(grid.iter_col(col).copied()))
+            .collect();
+        Self { rows, cols }
+    }
+
+    pub fn rows(&self) -> &[Vec<usize>] {
+        &self.rows
+    }
+
+    pub fn cols(&self) -> &[Vec<usize>] {
+        &self.cols
+    }
+
+    /// A grid of the puzzle's size with every cell unknown.
+    pub fn blank(&self) -> CustomGrid<char> {
+        CustomGrid::from_grid(Grid::init(self.rows.len(), self.cols.len(), UNKNOWN))
+    }
+
+    /// Line-solves rows and columns until no cell changes. Returns `false` if some line has no
+    /// arrangement left, in which case `grid` is left partially updated.
+    pub fn propagate(&self, grid: &mut CustomGrid<char>) -> bool {
+        assert_eq!(
+            grid.rows(),
+            self.rows.len(),
+            "grid rows do not match the clues"
+        );
+        assert_eq!(
+            grid.cols(),
+            self.cols.len(),
+            "grid columns do not match the clues"
+        );
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for (row, clues) in self.rows.iter().enumerate() {
+                let line: Vec<char> = grid.iter_row(row).copied().collect();
+                let Some(solved) = solve_line(&line, clues) else {
+                    return false;
+                };
+                if solved != line {
+                    changed = true;
+                    grid.iter_row_mut(row)
+                        .zip(solved)
+                        .for_each(|(cell, c)| *cell = c);
+                }
+            }
+
+            for (col, clues) in self.cols.iter().enumerate() {
+                let line: Vec<char> = grid.iter_col(col).copied().collect();
+                let Some(solved) = solve_line(&line, clues) else {
+                    return false;
+                };
+                if solved != line {
+                    changed = true;
+                    grid.iter_col_mut(col)
+                        .zip(solved)
+                        .for_each(|(cell, c)| *cell = c);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Calls `visit` with every solution extending `grid`, stopping early once it returns `false`.
+    pub fn for_each_solution(
+        &self,
+        grid: CustomGrid<char>,
+        visit: &mut impl FnMut(&CustomGrid<char>) -> bool,
+    ) -> bool {
+        let mut grid = grid;
+        if !self.propagate(&mut grid) {
+            return true;
+        }
+
+        let Some(index) = grid.iter().position(|&c| c == UNKNOWN) else {
+            return visit(&grid);
+        };
+        let (row, col) = (index / grid.cols(), index % grid.cols());
+
+        for guess in [FILLED, EMPTY] {
+            let mut branch = grid.clone();
+            *branch.get_mut(row, col).unwrap() = guess;
+            if !self.for_each_solution(branch, visit) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// First solution extending `grid`, if any.
+    pub fn solve(&self, grid: CustomGrid<char>) -> Option<CustomGrid<char>> {
+        self.solutions(grid, 1).pop()
+    }
+
+    /// Up to `limit` solutions extending `grid`.
+    pub fn solutions(&self, grid: CustomGrid<char>, limit: usize) -> Vec<CustomGrid<char>> {
+        let mut solutions = vec![];
+        if limit == 0 {
+            return solutions;
+        }
+
+        self.for_each_solution(grid, &mut |solution| {
+            solutions.push(solution.clone());
+            solutions.len() < limit
+        });
+        solutions
+    }
+
+    pub fn count_solutions(&self, grid: CustomGrid<char>) -> usize {
+        let mut count = 0;
+        self.for_each_solution(grid, &mut |_| {
+            count += 1;
+            true
+        });
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_grid::input_to_grid;
+
+    fn chars(line: &str) -> Vec<char> {
+        line.chars().collect()
+    }
+
+    #[test]
+    fn test_count_arrangements() {
+        assert_eq!(count_arrangements(&chars("???.###"), &[1, 1, 3]), 1);
+        assert_eq!(count_arrangements(&chars(".??..??...?##."), &[1, 1, 3]), 4);
+        assert_eq!(count_arrangements(&chars("?###????????"), &[3, 2, 1]), 10);
+        assert_eq!(count_arrangements(&chars("..."), &[]), 1);
+        assert_eq!(count_arrangements(&chars(".#."), &[]), 0);
+        assert_eq!(count_arrangements(&chars(""), &[1]), 0);
+    }
+
+    #[test]
+    fn test_solve_line() {
+        assert_eq!(
+            solve_line(&chars("??????????"), &[8]),
+            Some(chars("??######??"))
+        );
+        assert_eq!(
+            solve_line(&chars("?#????????"), &[3, 1]),
+            Some(chars("?##???????"))
+        );
+        assert_eq!(solve_line(&chars("???"), &[1, 1]), Some(chars("#.#")));
+        assert_eq!(solve_line(&chars("##."), &[1]), None);
+    }
+
+    #[test]
+    fn test_clues_of() {
+        assert_eq!(clues_of(chars(".##.#..###")), vec![2, 1, 3]);
+        assert_eq!(clues_of(chars("...")), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_solve_by_line_solving() {
+        let expected: CustomGrid<char> = input_to_grid(".#.\n###\n.#.\n").unwrap();
+        let nonogram = Nonogram::from_solution(&expected);
+        assert_eq!(nonogram.rows(), &[vec![1], vec![3], vec![1]]);
+
+        let mut grid = nonogram.blank();
+        assert!(nonogram.propagate(&mut grid));
+        assert_eq!(grid, expected);
+        assert_eq!(format!("{grid:?}"), ".#.\n###\n.#.\n");
+    }
+
+    #[test]
+    fn test_solve_with_backtracking() {
+        // a diagonal can be flipped, line solving alone cannot decide.
+        let nonogram = Nonogram::new(vec![vec![1], vec![1]], vec![vec![1], vec![1]]);
+        let mut grid = nonogram.blank();
+        assert!(nonogram.propagate(&mut grid));
+        assert_eq!(grid, nonogram.blank());
+
+        assert_eq!(nonogram.count_solutions(nonogram.blank()), 2);
+        let solutions = nonogram.solutions(nonogram.blank(), 5);
+        assert_eq!(format!("{:?}", solutions[0]), "#.\n.#\n");
+        assert_eq!(format!("{:?}", solutions[1]), ".#\n#.\n");
+
+        // a partial grid pins the solution.
+        let partial: CustomGrid<char> = input_to_grid("?#\n??\n").unwrap();
+        let solved = nonogram.solve(partial).unwrap();
+        assert_eq!(format!("{solved:?}"), ".#\n#.\n");
+    }
+
+    #[test]
+    fn test_unsolvable() {
+        let nonogram = Nonogram::new(vec![vec![2], vec![]], vec![vec![], vec![1]]);
+        assert_eq!(nonogram.solve(nonogram.blank()), None);
+        assert_eq!(nonogram.count_solutions(nonogram.blank()), 0);
+        assert!(nonogram.solutions(nonogram.blank(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_larger_puzzle() {
+        let expected: CustomGrid<char> = input_to_grid(
+            "\
+            .###.\n\
+            ##.##\n\
+            #####\n\
+            #...#\n\
+            ##.##\n",
+        )
+        .unwrap();
+        let nonogram = Nonogram::from_solution(&expected);
+        let solutions = nonogram.solutions(nonogram.blank(), 2);
+        assert_eq!(solutions, vec![expected]);
+    }
+}
@@ -0,0 +1,203 @@
+//! Multi-pattern string search with an Aho-Corasick automaton.
+//!
+//! All patterns are matched in a single pass over the haystack, including overlapping matches
+//! such as `"twone"` containing both `"two"` and `"one"`. Positions are byte offsets and patterns
+//! are identified by their index in the list the automaton was built from.
+use std::collections::VecDeque;
+
+const ROOT: usize = 0;
+
+#[derive(Clone, Debug)]
+struct State {
+    next: [usize; 256],
+    /// Patterns ending at this state, own pattern first, then those of the suffix links.
+    outputs: Vec<usize>,
+}
+
+/// An automaton matching a fixed set of patterns.
+#[derive(Clone, Debug)]
+pub struct AhoCorasick {
+    states: Vec<State>,
+    lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let mut states = vec![State {
+            next: [ROOT; 256],
+            outputs: vec![],
+        }];
+        let mut lengths = vec![];
+        // goto edges of the trie, the `next` tables are only complete once links are computed.
+        let mut has_edge = vec![[false; 256]];
+
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "patterns must not be empty");
+
+            let mut state = ROOT;
+            for &byte in pattern {
+                let byte = byte as usize;
+                if !has_edge[state][byte] {
+                    states.push(State {
+                        next: [ROOT; 256],
+                        outputs: vec![],
+                    });
+                    has_edge.push([false; 256]);
+                    has_edge[state][byte] = true;
+                    states[state].next[byte] = states.len() - 1;
+                }
+                state = states[state].next[byte];
+            }
+
+            states[state].outputs.push(id);
+            lengths.push(pattern.len());
+        }
+
+        // breadth first, so that a state's suffix link is complete before the state itself.
+        let mut links = vec![ROOT; states.len()];
+        let mut queue: VecDeque<usize> = (0..256)
+            .filter(|&byte| has_edge[ROOT][byte])
+            .map(|byte| states[ROOT].next[byte])
+            .collect();
+
+        while let Some(state) = queue.pop_front() {
+            let link = links[state];
+            let inherited = states[link].outputs.clone();
+            states[state].outputs.extend(inherited);
+
+            for (byte, &edge) in has_edge[state].iter().enumerate() {
+                if edge {
+                    let child = states[state].next[byte];
+                    links[child] = states[link].next[byte];
+                    queue.push_back(child);
+                } else {
+                    states[state].next[byte] = states[link].next[byte];
+                }
+            }
+        }
+
+        Self { states, lengths }
+    }
+
+    pub fn patterns_len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    pub fn pattern_len(&self, id: usize) -> usize {
+        self.lengths[id]
+    }
+
+    /// Iterates over every match as `(start, pattern_id)`, overlapping ones included.
+    ///
+    /// Matches come in order of their end position, longest first when several end together.
+    pub fn find_iter<'a, 'h>(&'a self, haystack: &'h str) -> Matches<'a, 'h> {
+        Matches {
+            automaton: self,
+            haystack: haystack.as_bytes(),
+            state: ROOT,
+            position: 0,
+            output: 0,
+        }
+    }
+
+    /// The match starting the earliest, preferring the lowest pattern id on ties.
+    pub fn find_first(&self, haystack: &str) -> Option<(usize, usize)> {
+        let longest = self.lengths.iter().copied().max()?;
+        let mut first: Option<(usize, usize)> = None;
+
+        for found in self.find_iter(haystack) {
+            match first {
+                // nothing ending from here on can start before the current best.
+                Some((first_start, _))
+                    if found.0 + self.lengths[found.1] > first_start + longest =>
+                {
+                    break
+                }
+                Some(best) if best <= found => {}
+                _ => first = Some(found),
+            }
+        }
+
+        first
+    }
+
+    /// The match starting the latest, preferring the lowest pattern id on ties.
+    pub fn find_last(&self, haystack: &str) -> Option<(usize, usize)> {
+        self.find_iter(haystack)
+            .max_by(|(start, id), (other_start, other_id)| {
+                start.cmp(other_start).then(other_id.cmp(id))
+            })
+    }
+}
+
+/// Iterator over the matches of an [`AhoCorasick`] automaton, see [`AhoCorasick::find_iter`].
+#[derive(Clone, Debug)]
+pub struct Matches<'a, 'h> {
+    automaton: &'a AhoCorasick,
+    haystack: &'h [u8],
+    state: usize,
+    position: usize,
+    output: usize,
+}
+
+impl Iterator for Matches<'_, '_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let outputs = &self.automaton.states[self.state].outputs;
+            if let Some(&id) = outputs.get(self.output) {
+                self.output += 1;
+                return Some((self.position - self.automaton.lengths[id], id));
+            }
+
+            let &byte = self.haystack.get(self.position)?;
+            self.state = self.automaton.states[self.state].next[byte as usize];
+            self.position += 1;
+            self.output = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_matches() {
+        let automaton = AhoCorasick::new(["he", "she", "his", "hers"]);
+        let matches: Vec<_> = automaton.find_iter("ushers").collect();
+        assert_eq!(matches, vec![(1, 1), (2, 0), (2, 3)]);
+    }
+
+    #[test]
+    fn test_spelled_digits() {
+        let automaton = AhoCorasick::new(["one", "two", "eight"]);
+        let matches: Vec<_> = automaton.find_iter("eightwone").collect();
+        assert_eq!(matches, vec![(0, 2), (4, 1), (6, 0)]);
+        assert_eq!(automaton.find_first("eightwone"), Some((0, 2)));
+        assert_eq!(automaton.find_last("eightwone"), Some((6, 0)));
+        assert_eq!(automaton.find_first("nothing"), None);
+    }
+
+    #[test]
+    fn test_find_first_prefers_earlier_start() {
+        // "bcd" ends first but "abcde" starts earlier.
+        let automaton = AhoCorasick::new(["bcd", "abcde", "a"]);
+        assert_eq!(automaton.find_first("xabcdex"), Some((1, 1)));
+
+        let automaton = AhoCorasick::new(["bcd", "abcde"]);
+        assert_eq!(automaton.find_first("xabcdex"), Some((1, 1)));
+        assert_eq!(automaton.find_last("xabcdex"), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_repeated_and_nested_patterns() {
+        let automaton = AhoCorasick::new(["aa", "a"]);
+        let matches: Vec<_> = automaton.find_iter("aaa").collect();
+        assert_eq!(matches, vec![(0, 1), (0, 0), (1, 1), (1, 0), (2, 1)]);
+        assert_eq!(automaton.patterns_len(), 2);
+        assert_eq!(automaton.pattern_len(0), 2);
+    }
+}
@@ -0,0 +1,181 @@
+/// A global allocator counting heap usage, installed by the `solution!` macro.
+///
+/// Counting is only enabled while [`measure`] runs, so benchmark samples pay for a single relaxed
+/// load per allocation. Counters are global: allocations of every thread are included.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+/// Bytes allocated and not yet freed since counting started, negative if older memory was freed.
+static LIVE: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+
+/// Heap usage of a measured function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included.
+    pub allocations: u64,
+    /// Total bytes requested by these allocations.
+    pub bytes: u64,
+    /// Largest amount of bytes live at once, on top of what was allocated before.
+    pub peak_bytes: u64,
+}
+
+/// [`System`] allocator that counts allocations while [`measure`] runs.
+pub struct CountingAlloc;
+
+fn record_alloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        record_live(size as i64);
+    }
+}
+
+fn record_live(delta: i64) {
+    let live = LIVE.fetch_add(delta, Ordering::Relaxed) + delta;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_alloc(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_alloc(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ENABLED.load(Ordering::Relaxed) {
+            record_live(-(layout.size() as i64));
+        }
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+            record_live(new_size as i64 - layout.size() as i64);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Runs `func` and counts its heap usage. Only meaningful with [`CountingAlloc`] installed as the
+/// global allocator, measurements must not overlap.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, AllocStats) {
+    for counter in [&ALLOCATIONS, &BYTES] {
+        counter.store(0, Ordering::Relaxed);
+    }
+    for counter in [&LIVE, &PEAK] {
+        counter.store(0, Ordering::Relaxed);
+    }
+
+    ENABLED.store(true, Ordering::SeqCst);
+    let result = func();
+    ENABLED.store(false, Ordering::SeqCst);
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK.load(Ordering::Relaxed).max(0) as u64,
+    };
+    (result, stats)
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+impl AllocStats {
+    /// Parses the stats printed by the runner, e.g. `[heap: 12 allocs, 4096 bytes, peak 1024 bytes]`.
+    pub fn parse(line: &str) -> Option<Self> {
+        let stats = line.split("[heap: ").nth(1)?.split(']').next()?;
+        let mut numbers = stats
+            .split(", ")
+            .map(|part| part.split(' ').find_map(|word| word.parse::<u64>().ok()));
+
+        Some(AllocStats {
+            allocations: numbers.next()??,
+            bytes: numbers.next()??,
+            peak_bytes: numbers.next()??,
+        })
+    }
+}
+
+/// Exact numbers, as printed next to timings for [`AllocStats::parse`].
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[heap: {} allocs, {} bytes, peak {} bytes]",
+            self.allocations, self.bytes, self.peak_bytes
+        )
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    // only in the lib's own test build, bins declare theirs through the `solution!` macro.
+    #[cfg(test)]
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn counts_allocations() {
+        let (_, stats) = measure(|| {
+            let mut kept = Vec::with_capacity(1000);
+            kept.push(std::hint::black_box(vec![0u8; 4000]));
+            drop(std::hint::black_box(vec![0u8; 8000]));
+            kept
+        });
+
+        // other tests allocate concurrently, so only lower bounds hold.
+        assert!(stats.allocations >= 3);
+        assert!(stats.bytes >= 1000 * 24 + 12000);
+        assert!(stats.peak_bytes >= 1000 * 24 + 8000 + 4000);
+    }
+
+    #[test]
+    fn parses_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak_bytes: 1024,
+        };
+        let line = format!("Part 1: 42 (1.0ms @ 10 samples) {stats}");
+        assert_eq!(AllocStats::parse(&line), Some(stats));
+        assert_eq!(AllocStats::parse("Part 1: 42 (1.0ms @ 10 samples)"), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
@@ -1,5 +1,6 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::limits::Limits;
+use crate::template::{run_multi::run_multi, DaySet};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(days: &DaySet, is_release: bool, limits: &Limits, jobs: usize) {
+    run_multi(&days.days(), is_release, false, limits, jobs);
 }
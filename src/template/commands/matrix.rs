@@ -0,0 +1,236 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use crate::template::run_multi::child_commands::{failure_kind, parse_answers};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{all_days, Day, DaySet, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, USER_ENV};
+
+/// Outcome of running one day against one user's input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    /// The solution did not run to completion, or a part failed.
+    Error,
+    /// There is no answer file to check the results against.
+    Unchecked,
+    NoInput,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Error => "ERROR",
+            Status::Unchecked => "?",
+            Status::NoInput => "-",
+        }
+    }
+}
+
+fn input_path(user: &str, day: Day) -> PathBuf {
+    Path::new("data/inputs")
+        .join(user)
+        .join(format!("{day}.txt"))
+}
+
+fn answers_path(user: &str, day: Day) -> PathBuf {
+    Path::new("data/answers")
+        .join(user)
+        .join(format!("{day}.txt"))
+}
+
+/// Users are the sub-directories of `data/inputs`, sorted by name.
+fn users() -> io::Result<Vec<String>> {
+    let mut users = vec![];
+    for entry in fs::read_dir("data/inputs")? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            users.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    users.sort();
+    Ok(users)
+}
+
+/// Compares the answers of a run to the expected ones, one line per part. Empty lines are parts
+/// without a known answer and are not checked.
+fn mismatches(expected: &str, answers: &[(String, String)]) -> Vec<String> {
+    expected
+        .lines()
+        .enumerate()
+        .filter(|(_, expected)| !expected.trim().is_empty())
+        .filter_map(|(i, expected)| {
+            let part = format!("Part {}", i + 1);
+            let answer = answers
+                .iter()
+                .find(|(answer_part, _)| *answer_part == part)
+                .map_or("nothing", |(_, answer)| answer.as_str());
+
+            (answer != expected.trim())
+                .then(|| format!("{part}: expected `{}`, got `{answer}`", expected.trim()))
+        })
+        .collect()
+}
+
+/// Runs the solution of a day against the input of a user, returning its status and the reasons
+/// of a failure.
+fn check(day: Day, user: &str, is_release: bool) -> (Status, Vec<String>) {
+    if !input_path(user, day).exists() {
+        return (Status::NoInput, vec![]);
+    }
+
+    let day_padded = day.to_string();
+    let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    if is_release {
+        args.push("--release");
+    }
+
+    let output = match Command::new("cargo")
+        .args(&args)
+        .env(USER_ENV, user)
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) => return (Status::Error, vec![format!("could not run cargo: {err}")]),
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lines: Vec<&str> = stderr.lines().collect();
+        // panic messages are printed on the line after their location.
+        let reason = match lines.iter().position(|line| line.contains("panicked")) {
+            Some(i) => lines[i..lines.len().min(i + 2)].join(" "),
+            None => lines
+                .iter()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .map_or("exited with an error".to_string(), |line| line.to_string()),
+        };
+        return (Status::Error, vec![reason]);
+    }
+
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+
+    // parts that returned an error or panicked, the solution itself kept running.
+    let failures: Vec<String> = answers
+        .iter()
+        .filter(|(_, answer)| failure_kind(answer).is_some())
+        .map(|(part, answer)| format!("{part}: {answer}"))
+        .collect();
+    if !failures.is_empty() {
+        return (Status::Error, failures);
+    }
+
+    let Ok(expected) = fs::read_to_string(answers_path(user, day)) else {
+        return (Status::Unchecked, vec![]);
+    };
+
+    match mismatches(&expected, &answers) {
+        mismatches if mismatches.is_empty() => (Status::Pass, vec![]),
+        mismatches => (Status::Fail, mismatches),
+    }
+}
+
+pub fn handle(days: &DaySet, is_release: bool) {
+    let users = users().unwrap_or_default();
+    if users.is_empty() {
+        eprintln!("No user inputs found, add them as `data/inputs/<user>/<day>.txt`.");
+        process::exit(1);
+    }
+
+    let selected = days.days();
+    let days: Vec<Day> = all_days()
+        .filter(|day| selected.contains(day))
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .filter(|day| users.iter().any(|user| input_path(user, *day).exists()))
+        .collect();
+
+    let mut rows = vec![];
+    let mut failures = vec![];
+
+    for day in days {
+        let mut row = vec![];
+        for user in &users {
+            print!("\r{ANSI_ITALIC}Running day {day} for {user}...{ANSI_RESET}\x1b[K");
+            let _ = io::stdout().flush();
+
+            let (status, reasons) = check(day, user, is_release);
+            failures.extend(
+                reasons
+                    .into_iter()
+                    .map(|reason| format!("Day {day}, {user}: {reason}")),
+            );
+            row.push(status);
+        }
+        rows.push((day, row));
+    }
+    print!("\r\x1b[K");
+
+    let width = users.iter().map(String::len).max().unwrap_or(0).max(5);
+    print!("{ANSI_BOLD}Day{ANSI_RESET}");
+    for user in &users {
+        print!("  {ANSI_BOLD}{user:<width$}{ANSI_RESET}");
+    }
+    println!();
+
+    for (day, row) in &rows {
+        print!("{day} ");
+        for status in row {
+            match status {
+                Status::Fail | Status::Error => {
+                    print!("  {ANSI_BOLD}{:<width$}{ANSI_RESET}", status.label());
+                }
+                _ => print!("  {:<width$}", status.label()),
+            }
+        }
+        println!();
+    }
+
+    if !failures.is_empty() {
+        println!();
+        for failure in &failures {
+            println!("{failure}");
+        }
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn answers(answers: &[(&str, &str)]) -> Vec<(String, String)> {
+        answers
+            .iter()
+            .map(|(part, answer)| (part.to_string(), answer.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn matches_answers() {
+        let run = answers(&[("Part 1", "142"), ("Part 2", "281")]);
+        assert!(mismatches("142\n281\n", &run).is_empty());
+        assert!(mismatches("142\n", &run).is_empty());
+        assert!(mismatches("\n281\n", &run).is_empty());
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let run = answers(&[("Part 1", "142"), ("Part 2", "✖")]);
+        assert_eq!(
+            mismatches("143\n281", &run),
+            vec![
+                "Part 1: expected `143`, got `142`",
+                "Part 2: expected `281`, got `✖`",
+            ]
+        );
+        assert_eq!(
+            mismatches("\n281", &answers(&[("Part 1", "142")])),
+            vec!["Part 2: expected `281`, got `nothing`"]
+        );
+    }
+}
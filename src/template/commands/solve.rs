@@ -1,8 +1,27 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{watch, Day, Input};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    profile_cpu: bool,
+    submit_part: Option<u8>,
+    watch: bool,
+    input: &Input,
+) {
+    if dhat && profile_cpu {
+        // both replace the build profile, and the CPU profile would include dhat's bookkeeping.
+        eprintln!("`--dhat` and `--profile-cpu` cannot be combined, run them separately.");
+        process::exit(1);
+    }
+
+    if watch {
+        watch::watch(day, release);
+        return;
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,17 +31,31 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
+    } else if profile_cpu {
+        cmd_args.extend([
+            "--profile".to_string(),
+            "profiling".to_string(),
+            "--features".to_string(),
+            "profile-cpu".to_string(),
+        ]);
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
     cmd_args.push("--".to_string());
 
+    if profile_cpu {
+        // the profiler samples the benchmark loop.
+        cmd_args.extend(["--time".to_string(), "--profile-cpu".to_string()]);
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -1,13 +1,18 @@
-use std::collections::HashSet;
+use std::process;
 
+use crate::template::history::{self, Environment, HistoryEntry};
+use crate::template::limits::Limits;
+use crate::template::readme_benchmarks::format_part;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, readme_benchmarks, Day, DaySet, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(days: Option<DaySet>, run_all: bool, store: bool, limits: &Limits) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = days.map_or_else(
         || {
             if run_all {
                 all_days().collect()
@@ -18,12 +23,16 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |days| days.resolve(&stored_timings),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, limits, 1).unwrap();
 
     if store {
+        if let Err(e) = history::append(&timings) {
+            eprintln!("Failed to append benchmarks to the history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
@@ -38,3 +47,205 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Runs from the first one at commit `from` to the last one at commit `to`, given as `from..to`.
+/// Without `..to`, runs from `from` up to the latest one.
+fn select_commits<'a>(
+    entries: &'a [HistoryEntry],
+    commits: &str,
+) -> Result<&'a [HistoryEntry], String> {
+    let (from, to) = match commits.split_once("..") {
+        Some((from, to)) => (from, Some(to)),
+        None => (commits, None),
+    };
+    // an empty ref would match every run.
+    if from.is_empty() || to.is_some_and(str::is_empty) {
+        return Err(format!(
+            "expected `<commit>` or `<commit>..<commit>`, got `{commits}`."
+        ));
+    }
+
+    let no_run = |commit: &str| format!("no stored run at commit `{commit}`.");
+
+    let start = entries
+        .iter()
+        .position(|entry| entry.environment.is_at_commit(from))
+        .ok_or_else(|| no_run(from))?;
+    let end = match to {
+        Some(to) => entries
+            .iter()
+            .rposition(|entry| entry.environment.is_at_commit(to))
+            .ok_or_else(|| no_run(to))?,
+        None => entries.len() - 1,
+    };
+
+    // only possible with `to`, the latest run is never before `from`.
+    if end < start {
+        let to = to.unwrap_or_default();
+        return Err(format!("`{to}` was benched before `{from}`."));
+    }
+    Ok(&entries[start..=end])
+}
+
+/// Relative change between two totals, e.g. `-12.5%`.
+fn format_change(previous: f64, current: f64) -> String {
+    if previous > 0.0 {
+        format!("{:+.1}%", (current - previous) / previous * 100.0)
+    } else {
+        String::new()
+    }
+}
+
+fn format_commit(environment: &Environment) -> String {
+    let commit = environment.commit.as_deref().unwrap_or("-");
+    if environment.dirty {
+        format!("{commit}*")
+    } else {
+        commit.to_string()
+    }
+}
+
+/// Parts of the environment that differ from the previous run, all of them for the first run.
+fn environment_changes(previous: Option<&Environment>, current: &Environment) -> Vec<String> {
+    let fields = |environment: &Environment| [environment.rustc.clone(), environment.cpu.clone()];
+
+    let current_fields = fields(current);
+    let previous_fields = previous.map(fields);
+
+    current_fields
+        .into_iter()
+        .enumerate()
+        .filter(|(i, field)| previous_fields.as_ref().is_none_or(|p| p[*i] != *field))
+        .filter_map(|(_, field)| field)
+        .collect()
+}
+
+/// Prints how the runtime of a day evolved over the runs stored with `cargo time --store`.
+pub fn handle_history(day: Day, commits: Option<&str>) {
+    let entries: Vec<HistoryEntry> = history::read_from_file()
+        .into_iter()
+        .filter(|entry| entry.timing(day).is_some())
+        .collect();
+
+    if entries.is_empty() {
+        eprintln!("No stored benchmarks of day {day}, store some with `cargo time {day} --store`.");
+        process::exit(1);
+    }
+
+    let entries = match commits.map(|commits| select_commits(&entries, commits)) {
+        Some(Ok(entries)) => entries,
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => &entries,
+    };
+
+    println!(
+        "{ANSI_BOLD}{:<17} {:<10} {:<12} {:<12} {:<10} Change{ANSI_RESET}",
+        "Date", "Commit", "Part 1", "Part 2", "Total"
+    );
+
+    let mut previous: Option<&HistoryEntry> = None;
+    for entry in entries {
+        let timing = entry.timing(day).unwrap();
+        let date = entry.environment.date.replace('T', " ");
+        let total_millis = timing.total_nanos / 1_000_000_f64;
+        let change = previous.map_or(String::new(), |previous| {
+            format_change(
+                previous.timing(day).unwrap().total_nanos,
+                timing.total_nanos,
+            )
+        });
+
+        println!(
+            "{:<17} {:<10} {:<12} {:<12} {:<10} {change}",
+            date.get(..16).unwrap_or(&date),
+            format_commit(&entry.environment),
+            format_part(timing.part_1.clone(), timing.failures[0]),
+            format_part(timing.part_2.clone(), timing.failures[1]),
+            format!("{total_millis:.2}ms"),
+        );
+
+        for change in environment_changes(
+            previous.map(|previous| &previous.environment),
+            &entry.environment,
+        ) {
+            println!("  {ANSI_ITALIC}{change}{ANSI_RESET}");
+        }
+
+        previous = Some(entry);
+    }
+
+    if let (Some(first), Some(last)) = (entries.first(), entries.last()) {
+        if entries.len() > 1 {
+            let (first_nanos, last_nanos) = (
+                first.timing(day).unwrap().total_nanos,
+                last.timing(day).unwrap().total_nanos,
+            );
+            println!(
+                "\n{ANSI_BOLD}{} → {}:{ANSI_RESET} {ANSI_ITALIC}{:.2}ms → {:.2}ms{ANSI_RESET} {}",
+                format_commit(&first.environment),
+                format_commit(&last.environment),
+                first_nanos / 1_000_000_f64,
+                last_nanos / 1_000_000_f64,
+                format_change(first_nanos, last_nanos)
+            );
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn entry(commit: &str) -> HistoryEntry {
+        HistoryEntry {
+            environment: Environment {
+                commit: Some(commit.into()),
+                ..Environment::default()
+            },
+            timings: Timings::default(),
+        }
+    }
+
+    #[test]
+    fn selects_commit_ranges() {
+        let entries = [
+            entry("aaa1111"),
+            entry("bbb2222"),
+            entry("bbb2222"),
+            entry("ccc3333"),
+        ];
+        let commits = |range: &str| {
+            select_commits(&entries, range).map(|entries| {
+                entries
+                    .iter()
+                    .map(|entry| entry.environment.commit.clone().unwrap())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(commits("aaa").unwrap().len(), 4);
+        assert_eq!(commits("bbb").unwrap().len(), 3);
+        assert_eq!(
+            commits("bbb..ccc").unwrap(),
+            vec!["bbb2222", "bbb2222", "ccc3333"]
+        );
+        assert_eq!(commits("aaa1111..bbb").unwrap().len(), 3);
+        assert_eq!(commits("bbb..bbb").unwrap().len(), 2);
+        assert!(commits("ccc..aaa").is_err());
+        assert!(commits("ddd").is_err());
+
+        for empty in ["", "..", "..ccc", "aaa.."] {
+            assert!(commits(empty).is_err());
+        }
+    }
+
+    #[test]
+    fn formats_changes() {
+        assert_eq!(format_change(2.0, 1.5), "-25.0%");
+        assert_eq!(format_change(1.0, 1.1), "+10.0%");
+        assert_eq!(format_change(0.0, 1.0), "");
+    }
+}
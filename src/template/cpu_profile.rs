@@ -0,0 +1,97 @@
+/// Sampling CPU profiler for `cargo solve <day> --profile-cpu`.
+///
+/// Call stacks are sampled while a part is benched and written to `data/profiles` as folded
+/// stacks (`<day>-part<N>.folded`) and a flamegraph (`<day>-part<N>.svg`).
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use pprof::flamegraph::{self, Options};
+use pprof::{ProfilerGuardBuilder, Report};
+
+use crate::template::Day;
+
+static PROFILES_PATH: &str = "data/profiles";
+
+/// Samples per second, a prime to avoid sampling in lockstep with periodic work.
+const FREQUENCY: i32 = 997;
+
+/// Runs `func` while sampling its call stacks, then writes the profile of the part.
+pub fn profile<T>(day: Day, part: u8, func: impl FnOnce() -> T) -> T {
+    let guard = ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build();
+
+    let guard = match guard {
+        Ok(guard) => guard,
+        Err(err) => {
+            eprintln!("Could not start the CPU profiler: {err}");
+            return func();
+        }
+    };
+
+    let result = func();
+
+    match guard.report().build() {
+        Ok(report) => match write_profile(&report, day, part) {
+            Ok(path) => eprintln!("\nWrote CPU profile to {}.", path.display()),
+            Err(err) => eprintln!("\nCould not write the CPU profile: {err}"),
+        },
+        Err(err) => eprintln!("\nCould not build the CPU profile: {err}"),
+    }
+
+    result
+}
+
+/// Stacks of a report as `thread;outermost;...;innermost <samples>` lines, sorted for stable
+/// diffs between runs.
+fn folded_stacks(report: &Report) -> Vec<String> {
+    let mut lines: Vec<String> = report
+        .data
+        .iter()
+        .map(|(frames, samples)| {
+            let stack: Vec<String> = frames
+                .frames
+                .iter()
+                .rev()
+                .flat_map(|symbols| symbols.iter().rev().map(ToString::to_string))
+                .collect();
+            format!(
+                "{};{} {samples}",
+                frames.thread_name_or_id(),
+                stack.join(";")
+            )
+        })
+        .collect();
+    lines.sort();
+    lines
+}
+
+/// Writes the folded stacks and flamegraph, returning the path of the flamegraph.
+fn write_profile(report: &Report, day: Day, part: u8) -> Result<PathBuf, Box<dyn Error>> {
+    fs::create_dir_all(PROFILES_PATH)?;
+    let path = Path::new(PROFILES_PATH).join(format!("{day}-part{part}"));
+    let lines = folded_stacks(report);
+    if lines.is_empty() {
+        return Err("no samples were taken, the part finished too quickly.".into());
+    }
+
+    let mut folded = BufWriter::new(File::create(path.with_extension("folded"))?);
+    for line in &lines {
+        writeln!(folded, "{line}")?;
+    }
+    folded.flush()?;
+
+    let svg_path = path.with_extension("svg");
+    let mut options = Options::default();
+    options.title = format!("Day {day}, part {part}");
+    flamegraph::from_lines(
+        &mut options,
+        lines.iter().map(String::as_str),
+        File::create(&svg_path)?,
+    )?;
+
+    Ok(svg_path)
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -5,6 +6,8 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, Local};
 
+use crate::template::timings::Timings;
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -96,6 +99,192 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+/// Selects days by number or by their stored timings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaySelector {
+    /// Days from the first to the second one, both included.
+    Range(Day, Day),
+    /// Days without a stored timing for both parts.
+    Unsolved,
+    /// Days with a failed part in the stored timings.
+    Failing,
+    /// The given number of days with the longest stored total time.
+    Slowest(usize),
+}
+
+/// A set of days given as comma separated selectors, e.g. `1-10,!5`, `3,5,7`, `unsolved` or
+/// `slowest:5`. Selectors prefixed with `!` are removed from the set, a set of only removals
+/// starts with every day.
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1-10,!5".parse().unwrap();
+/// assert_eq!(days.to_string(), "01-10,!05");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet {
+    include: Vec<DaySelector>,
+    exclude: Vec<DaySelector>,
+}
+
+impl DaySet {
+    /// Every day of advent.
+    pub fn all() -> Self {
+        Self {
+            include: vec![DaySelector::Range(Day(1), Day(25))],
+            exclude: vec![],
+        }
+    }
+
+    /// Days selected by this set, stored timings are only read if a selector needs them.
+    pub(crate) fn days(&self) -> HashSet<Day> {
+        let uses_timings = self
+            .include
+            .iter()
+            .chain(&self.exclude)
+            .any(|selector| !matches!(selector, DaySelector::Range(..)));
+
+        if uses_timings {
+            self.resolve(&Timings::read_from_file())
+        } else {
+            self.resolve(&Timings::default())
+        }
+    }
+
+    /// Days selected by this set, selectors based on timings are resolved with `timings`.
+    pub(crate) fn resolve(&self, timings: &Timings) -> HashSet<Day> {
+        let select = |selector: &DaySelector| -> Vec<Day> {
+            match *selector {
+                DaySelector::Range(from, to) => all_days()
+                    .filter(|day| *day >= from && *day <= to)
+                    .collect(),
+                DaySelector::Unsolved => all_days()
+                    .filter(|day| !timings.is_day_complete(*day))
+                    .collect(),
+                DaySelector::Failing => timings
+                    .data
+                    .iter()
+                    .filter(|timing| timing.failures.iter().any(Option::is_some))
+                    .map(|timing| timing.day)
+                    .collect(),
+                DaySelector::Slowest(count) => {
+                    let mut data: Vec<_> = timings.data.iter().collect();
+                    data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+                    data.iter().take(count).map(|timing| timing.day).collect()
+                }
+            }
+        };
+
+        let mut days: HashSet<Day> = if self.include.is_empty() {
+            all_days().collect()
+        } else {
+            self.include.iter().flat_map(select).collect()
+        };
+        for day in self.exclude.iter().flat_map(select) {
+            days.remove(&day);
+        }
+        days
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self {
+            include: vec![DaySelector::Range(day, day)],
+            exclude: vec![],
+        }
+    }
+}
+
+impl FromStr for DaySelector {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DaySetFromStrError(s.to_string());
+
+        match s {
+            "unsolved" => return Ok(Self::Unsolved),
+            "failing" => return Ok(Self::Failing),
+            _ => {}
+        }
+
+        if let Some(count) = s.strip_prefix("slowest:") {
+            return count.parse().map(Self::Slowest).map_err(|_| invalid());
+        }
+
+        let (from, to) = s.split_once('-').unwrap_or((s, s));
+        let from: Day = from.trim().parse().map_err(|_| invalid())?;
+        let to: Day = to.trim().parse().map_err(|_| invalid())?;
+        if from > to {
+            return Err(invalid());
+        }
+        Ok(Self::Range(from, to))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self {
+            include: vec![],
+            exclude: vec![],
+        };
+
+        for term in s.split(',').map(str::trim) {
+            match term.strip_prefix('!') {
+                Some(term) => set.exclude.push(term.parse()?),
+                None => set.include.push(term.parse()?),
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+impl Display for DaySelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Range(from, to) if from == to => write!(f, "{from}"),
+            Self::Range(from, to) => write!(f, "{from}-{to}"),
+            Self::Unsolved => f.write_str("unsolved"),
+            Self::Failing => f.write_str("failing"),
+            Self::Slowest(count) => write!(f, "slowest:{count}"),
+        }
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<String> = self
+            .include
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.exclude.iter().map(|selector| format!("!{selector}")))
+            .collect();
+        f.write_str(&terms.join(","))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`], with the invalid selector.
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selector `{}`, expecting a day, a range like `1-10`, `unsolved`, \
+            `failing` or `slowest:<count>`, optionally prefixed with `!`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -150,7 +339,9 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
+    use crate::day;
+    use crate::template::timings::{FailureKind, Timing, Timings};
 
     #[test]
     fn all_days_iterator() {
@@ -183,6 +374,76 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    fn resolve(set: &str, timings: &Timings) -> Vec<u8> {
+        let mut days: Vec<u8> = set
+            .parse::<DaySet>()
+            .unwrap()
+            .resolve(timings)
+            .into_iter()
+            .map(Day::into_inner)
+            .collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn day_set_ranges() {
+        let timings = Timings::default();
+        assert_eq!(resolve("1-3", &timings), vec![1, 2, 3]);
+        assert_eq!(resolve("3,5,7", &timings), vec![3, 5, 7]);
+        assert_eq!(resolve("1-4,!2, 10", &timings), vec![1, 3, 4, 10]);
+        assert_eq!(resolve("!2-24", &timings), vec![1, 25]);
+        assert_eq!(DaySet::from(day!(4)).resolve(&timings).len(), 1);
+        assert_eq!(DaySet::all().resolve(&timings).len(), 25);
+    }
+
+    #[test]
+    fn day_set_timing_selectors() {
+        let timing = |day: Day, total_nanos: f64, failures: [Option<FailureKind>; 2]| Timing {
+            day,
+            part_1: Some("1ms".into()),
+            part_2: failures[1].is_none().then(|| "1ms".into()),
+            total_nanos,
+            failures,
+            heap: [None, None],
+        };
+        let timings = Timings {
+            data: vec![
+                timing(day!(1), 3.0, [None, None]),
+                timing(day!(2), 1.0, [None, Some(FailureKind::Timeout)]),
+                timing(day!(3), 2.0, [None, None]),
+            ],
+        };
+
+        assert_eq!(resolve("failing", &timings), vec![2]);
+        assert_eq!(resolve("slowest:2", &timings), vec![1, 3]);
+        assert_eq!(resolve("slowest:2,!1", &timings), vec![3]);
+        assert_eq!(resolve("unsolved,!5-25", &timings), vec![2, 4]);
+    }
+
+    #[test]
+    fn day_set_errors() {
+        for set in [
+            "",
+            "0",
+            "26",
+            "5-3",
+            "1-",
+            "slowest:",
+            "slowest:x",
+            "solved",
+        ] {
+            assert!(set.parse::<DaySet>().is_err(), "{set}");
+        }
+        assert_eq!(
+            "1-10,failing,!slowest:3"
+                .parse::<DaySet>()
+                .unwrap()
+                .to_string(),
+            "01-10,failing,!slowest:3"
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,277 @@
+/// History of stored benchmarks, one JSON line per `cargo time --store` run.
+///
+/// Unlike `data/timings.json`, which keeps the latest timing of every day, runs are only ever
+/// appended to `data/timings-history.jsonl`, together with the environment they ran in.
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::Day;
+
+static HISTORY_FILE_PATH: &str = "./data/timings-history.jsonl";
+
+/// Where and when a benchmark run happened. Fields that could not be determined are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Environment {
+    /// UTC time of the run, e.g. `2023-12-24T18:30:00Z`.
+    pub date: String,
+    /// Short hash of the checked out commit.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    pub cpu: Option<String>,
+}
+
+/// A single `cargo time --store` run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub environment: Environment,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Timing of a day in this run, if it was benched.
+    pub fn timing(&self, day: Day) -> Option<&Timing> {
+        self.timings.data.iter().find(|timing| timing.day == day)
+    }
+}
+
+/// Appends the timings of a run to the history file.
+pub fn append(timings: &Timings) -> Result<(), Error> {
+    let entry = HistoryEntry {
+        environment: Environment::current(),
+        timings: timings.clone(),
+    };
+    let line = JsonValue::from(&entry)
+        .stringify()
+        .map_err(|e| Error::other(e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+    writeln!(file, "{line}")
+}
+
+/// Runs of the history file in the order they were stored. Invalid lines are skipped.
+pub fn read_from_file() -> Vec<HistoryEntry> {
+    let Ok(s) = fs::read_to_string(HISTORY_FILE_PATH) else {
+        return vec![];
+    };
+
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| {
+            HistoryEntry::try_from(line)
+                .map_err(|e| eprintln!("Skipping line {} of {HISTORY_FILE_PATH}: {e}", i + 1))
+                .ok()
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Trimmed stdout of a command, `None` if it could not run or failed.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    (output.status.success() && !stdout.trim().is_empty()).then(|| stdout.trim().to_string())
+}
+
+fn cpu_model() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        return command_output("sysctl", &["-n", "machdep.cpu.brand_string"]);
+    }
+
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "model name").then(|| value.trim().to_string())
+    })
+}
+
+/// Formats seconds since the unix epoch as an ISO 8601 UTC date.
+fn format_date(unix_secs: u64) -> String {
+    let (days, secs) = (unix_secs / 86400, unix_secs % 86400);
+
+    // civil date from days since epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+impl Environment {
+    /// Environment of the current run.
+    pub fn current() -> Self {
+        let unix_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Environment {
+            date: format_date(unix_secs),
+            commit: command_output("git", &["rev-parse", "--short", "HEAD"]),
+            dirty: command_output("git", &["status", "--porcelain", "--untracked-files=no"])
+                .is_some(),
+            rustc: command_output("rustc", &["--version"]),
+            cpu: cpu_model(),
+        }
+    }
+
+    /// Whether the run was at `commit`, given as a full or abbreviated hash.
+    pub fn is_at_commit(&self, commit: &str) -> bool {
+        !commit.is_empty()
+            && self
+                .commit
+                .as_deref()
+                .is_some_and(|own| own.starts_with(commit) || commit.starts_with(own))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let env = &value.environment;
+        let optional = |value: &Option<String>| match value {
+            Some(value) => JsonValue::String(value.clone()),
+            None => JsonValue::Null,
+        };
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("date".into(), JsonValue::String(env.date.clone()));
+        map.insert("commit".into(), optional(&env.commit));
+        map.insert("dirty".into(), JsonValue::Boolean(env.dirty));
+        map.insert("rustc".into(), optional(&env.rustc));
+        map.insert("cpu".into(), optional(&env.cpu));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected entry to be an object.")?;
+
+        let optional = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let environment = Environment {
+            date: optional("date").ok_or("expected `date` to be a string.")?,
+            commit: optional("commit"),
+            dirty: json
+                .get("dirty")
+                .and_then(|v| v.get::<bool>())
+                .copied()
+                .unwrap_or_default(),
+            rustc: optional("rustc"),
+            cpu: optional("cpu"),
+        };
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `data` to be an array.")?;
+
+        Ok(HistoryEntry {
+            environment,
+            timings: Timings {
+                data: data
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_date(1_703_442_600), "2023-12-24T18:30:00Z");
+        assert_eq!(format_date(951_825_600), "2000-02-29T12:00:00Z");
+    }
+
+    #[test]
+    fn serializes_entries() {
+        let entry = HistoryEntry {
+            environment: Environment {
+                date: "2023-12-24T18:30:00Z".into(),
+                commit: Some("abc1234".into()),
+                dirty: true,
+                rustc: Some("rustc 1.74.0 (79e9716c9 2023-11-13)".into()),
+                cpu: None,
+            },
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(12),
+                    part_1: Some("1.5ms".into()),
+                    part_2: None,
+                    total_nanos: 1_500_000.0,
+                    failures: [None, None],
+                    heap: [None, None],
+                }],
+            },
+        };
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert!(!line.contains('\n'));
+
+        let parsed = HistoryEntry::try_from(line.as_str()).unwrap();
+        assert_eq!(parsed.environment, entry.environment);
+        assert_eq!(
+            parsed.timing(day!(12)).unwrap().part_1.as_deref(),
+            Some("1.5ms")
+        );
+        assert!(parsed.timing(day!(1)).is_none());
+    }
+
+    #[test]
+    fn matches_abbreviated_commits() {
+        let environment = Environment {
+            commit: Some("abc1234".into()),
+            ..Environment::default()
+        };
+        assert!(environment.is_at_commit("abc1"));
+        assert!(environment.is_at_commit("abc1234def"));
+        assert!(!environment.is_at_commit("abd"));
+        assert!(!environment.is_at_commit(""));
+        assert!(!Environment::default().is_at_commit("abc"));
+    }
+}
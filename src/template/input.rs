@@ -0,0 +1,183 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io::Read;
+use std::path::PathBuf;
+use std::{env, fs, io, process};
+
+use crate::template::{read_file, read_file_part, Day};
+
+/// Where a solution binary reads its input from, selected with `--input <path>`, `--input -`
+/// or `--example [N]`. Without any of these, the puzzle input of the day is used.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Input {
+    /// `data/inputs/DD.txt`, or `data/inputs/<user>/DD.txt` with `AOC_USER` set.
+    #[default]
+    Puzzle,
+    /// `data/examples/DD.txt`, or `data/examples/DD-N.txt` for `--example N`.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    /// Parses the input selection out of command-line arguments, other arguments are ignored.
+    pub fn from_args(args: &[String]) -> Result<Self, InputArgsError> {
+        // a following flag is not a value, e.g. `--example --time` selects no part.
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .map(|index| args.get(index + 1).filter(|value| !value.starts_with("--")))
+        };
+
+        let path = match value_of("--input") {
+            Some(None) => return Err(InputArgsError::MissingPath),
+            path => path.flatten().map(String::as_str),
+        };
+        let example = match value_of("--example") {
+            Some(Some(part)) => Some(Some(
+                part.parse()
+                    .map_err(|_| InputArgsError::InvalidPart(part.clone()))?,
+            )),
+            example => example.map(|_| None),
+        };
+
+        Self::new(path, example)
+    }
+
+    /// Input for the value of `--input` and the optional part of `--example`, if present. A path
+    /// starting with `--` is taken for a flag that followed `--input` without a value.
+    pub fn new(path: Option<&str>, example: Option<Option<u8>>) -> Result<Self, InputArgsError> {
+        match (path, example) {
+            (Some(path), _) if path.starts_with("--") => Err(InputArgsError::MissingPath),
+            (Some(_), Some(_)) => Err(InputArgsError::Conflicting),
+            (Some("-"), None) => Ok(Self::Stdin),
+            (Some(path), None) => Ok(Self::File(path.into())),
+            (None, Some(part)) => Ok(Self::Example(part)),
+            (None, None) => Ok(Self::Puzzle),
+        }
+    }
+
+    /// The input selection of the current process, exits on invalid arguments.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::from_args(&args).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            process::exit(1);
+        })
+    }
+
+    /// Arguments that select this input when passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(part)) => vec!["--example".into(), part.to_string()],
+            Self::File(path) => vec!["--input".into(), path.to_string_lossy().into_owned()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// # Panics
+    /// If the input can not be read.
+    #[must_use]
+    pub fn read(&self, day: Day) -> String {
+        match self {
+            Self::Puzzle => read_file("inputs", day),
+            Self::Example(None) => read_file("examples", day),
+            Self::Example(Some(part)) => read_file_part("examples", day, *part),
+            Self::File(path) => fs::read_to_string(path).expect("could not open input file"),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .expect("could not read input from stdin");
+                input
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputArgsError {
+    MissingPath,
+    InvalidPart(String),
+    Conflicting,
+}
+
+impl Display for InputArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingPath => write!(f, "`--input` expects a path, or `-` to read from stdin."),
+            Self::InvalidPart(part) => {
+                write!(f, "`--example` expects a part number, got `{part}`.")
+            }
+            Self::Conflicting => write!(f, "`--input` and `--example` can not be combined."),
+        }
+    }
+}
+
+impl Error for InputArgsError {}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Input, InputArgsError> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        Input::from_args(&args)
+    }
+
+    #[test]
+    fn parses_inputs() {
+        assert_eq!(parse("01").unwrap(), Input::Puzzle);
+        assert_eq!(parse("01 --input -").unwrap(), Input::Stdin);
+        assert_eq!(
+            parse("01 --input edge.txt --time").unwrap(),
+            Input::File("edge.txt".into())
+        );
+        assert_eq!(parse("01 --example").unwrap(), Input::Example(None));
+        assert_eq!(parse("01 --example --time").unwrap(), Input::Example(None));
+        assert_eq!(parse("01 --example 2").unwrap(), Input::Example(Some(2)));
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        assert!(matches!(
+            parse("01 --input"),
+            Err(InputArgsError::MissingPath)
+        ));
+        assert!(matches!(
+            parse("01 --input a.txt --example"),
+            Err(InputArgsError::Conflicting)
+        ));
+        assert!(matches!(
+            parse("01 --input --example"),
+            Err(InputArgsError::MissingPath)
+        ));
+        assert!(matches!(
+            parse("01 --example --input a.txt"),
+            Err(InputArgsError::Conflicting)
+        ));
+        assert!(matches!(
+            parse("01 --example a.txt"),
+            Err(InputArgsError::InvalidPart(part)) if part == "a.txt"
+        ));
+        assert!(matches!(
+            Input::new(Some("--example"), None),
+            Err(InputArgsError::MissingPath)
+        ));
+    }
+
+    #[test]
+    fn round_trips_args() {
+        for input in [
+            Input::Puzzle,
+            Input::Stdin,
+            Input::File("a.txt".into()),
+            Input::Example(None),
+            Input::Example(Some(1)),
+        ] {
+            assert_eq!(Input::from_args(&input.to_args()).unwrap(), input);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+/// Resource limits of solution runs in `cargo all` and `cargo time`.
+///
+/// Limits are read from `data/limits.json`, a missing file means no limits. Day overrides are
+/// keyed by `DD`, or by `DD-N` for the timeout of a single part:
+/// ```json
+/// { "timeout_secs": 10, "memory_mb": 4096, "days": { "05": { "timeout_secs": 60 } } }
+/// ```
+use std::{collections::HashMap, fs, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static LIMITS_FILE_PATH: &str = "./data/limits.json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Limit {
+    timeout: Option<Duration>,
+    memory_bytes: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Limits {
+    default: Limit,
+    overrides: HashMap<String, Limit>,
+}
+
+impl Limits {
+    /// Limits of `data/limits.json`, or none if the file is missing or invalid.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(LIMITS_FILE_PATH) else {
+            return Limits::default();
+        };
+
+        Limits::try_from(s).unwrap_or_else(|e| {
+            eprintln!("Ignoring {LIMITS_FILE_PATH}: {e}");
+            Limits::default()
+        })
+    }
+
+    /// Replaces the default timeout, day overrides still apply.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.default.timeout = timeout.or(self.default.timeout);
+        self
+    }
+
+    /// Replaces the default memory limit, day overrides still apply.
+    pub fn with_memory_bytes(mut self, memory_bytes: Option<u64>) -> Self {
+        self.default.memory_bytes = memory_bytes.or(self.default.memory_bytes);
+        self
+    }
+
+    /// Wall-clock time a part may take, benchmarks included.
+    pub fn timeout(&self, day: Day, part: u8) -> Option<Duration> {
+        [format!("{day}-{part}"), day.to_string()]
+            .iter()
+            .find_map(|key| self.overrides.get(key).and_then(|limit| limit.timeout))
+            .or(self.default.timeout)
+    }
+
+    /// Virtual memory (address space) a day's process may reserve, see `setrlimit(RLIMIT_AS)`.
+    pub fn memory_bytes(&self, day: Day) -> Option<u64> {
+        self.overrides
+            .get(&day.to_string())
+            .and_then(|limit| limit.memory_bytes)
+            .or(self.default.memory_bytes)
+    }
+
+    /// Whether any limit applies to the day.
+    pub fn is_limited(&self, day: Day) -> bool {
+        self.memory_bytes(day).is_some() || (1..=2).any(|part| self.timeout(day, part).is_some())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&HashMap<String, JsonValue>> for Limit {
+    type Error = String;
+
+    fn try_from(json: &HashMap<String, JsonValue>) -> Result<Self, Self::Error> {
+        let number = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    v.get::<f64>()
+                        .copied()
+                        .filter(|n| *n > 0.0)
+                        .ok_or(format!("expected `{key}` to be a positive number."))
+                })
+                .transpose()
+        };
+
+        Ok(Limit {
+            timeout: number("timeout_secs")?.map(Duration::from_secs_f64),
+            memory_bytes: number("memory_mb")?.map(|mb| (mb * 1024.0 * 1024.0) as u64),
+        })
+    }
+}
+
+impl TryFrom<String> for Limits {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut overrides = HashMap::new();
+        if let Some(days) = json.get("days") {
+            let days = days
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected `days` to be an object.")?;
+
+            for (key, limit) in days {
+                let limit = limit
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or(format!("expected `days.{key}` to be an object."))?;
+                overrides.insert(key.clone(), Limit::try_from(limit)?);
+            }
+        }
+
+        Ok(Limits {
+            default: Limit::try_from(json)?,
+            overrides,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Limits;
+    use crate::day;
+
+    #[test]
+    fn handles_missing_limits() {
+        let limits = Limits::try_from(r#"{}"#.to_string()).unwrap();
+        assert_eq!(limits, Limits::default());
+        assert!(!limits.is_limited(day!(1)));
+    }
+
+    #[test]
+    fn resolves_overrides() {
+        let json = r#"{ "timeout_secs": 10, "memory_mb": 1, "days": { "05": { "timeout_secs": 60, "memory_mb": 2 }, "05-2": { "timeout_secs": 0.5 } } }"#;
+        let limits = Limits::try_from(json.to_string()).unwrap();
+
+        assert_eq!(limits.timeout(day!(1), 1), Some(Duration::from_secs(10)));
+        assert_eq!(limits.timeout(day!(5), 1), Some(Duration::from_secs(60)));
+        assert_eq!(limits.timeout(day!(5), 2), Some(Duration::from_millis(500)));
+        assert_eq!(limits.memory_bytes(day!(1)), Some(1024 * 1024));
+        assert_eq!(limits.memory_bytes(day!(5)), Some(2 * 1024 * 1024));
+    }
+
+    #[test]
+    fn overrides_defaults_from_args() {
+        let json = r#"{ "timeout_secs": 10, "days": { "05": { "timeout_secs": 60 } } }"#;
+        let limits = Limits::try_from(json.to_string())
+            .unwrap()
+            .with_timeout(Some(Duration::from_secs(1)))
+            .with_memory_bytes(None);
+
+        assert_eq!(limits.timeout(day!(1), 1), Some(Duration::from_secs(1)));
+        assert_eq!(limits.timeout(day!(5), 1), Some(Duration::from_secs(60)));
+        assert_eq!(limits.memory_bytes(day!(1)), None);
+        assert!(limits.is_limited(day!(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_limits() {
+        Limits::try_from(r#"{ "days": { "05": { "timeout_secs": "1m" } } }"#.to_string()).unwrap();
+    }
+}
@@ -1,25 +1,50 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod limits;
 pub mod runner;
 
 pub use day::*;
+pub use input::*;
 
+#[cfg(all(feature = "profile-cpu", unix))]
+mod cpu_profile;
 mod day;
+mod history;
+mod input;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Environment variable selecting whose puzzle inputs are read, see [`read_file`].
+pub const USER_ENV: &str = "AOC_USER";
+
+/// The user set in [`USER_ENV`], if any.
+#[must_use]
+pub fn input_user() -> Option<String> {
+    env::var(USER_ENV).ok().filter(|user| !user.is_empty())
+}
+
 /// Helper function that reads a text file to a string.
+///
+/// Inputs are read from `data/inputs/<user>/<day>.txt` instead when a user is set in [`USER_ENV`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let mut filepath = cwd.join("data").join(folder);
+    if folder == "inputs" {
+        if let Some(user) = input_user() {
+            filepath.push(user);
+        }
+    }
+    let filepath = filepath.join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -39,6 +64,11 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The generated `main` reads the puzzle input, unless another one is selected with
+/// `--input <path>`, `--input -` (stdin) or `--example [N]`.
+///
+/// Heap usage is counted by [`alloc::CountingAlloc`], unless `dhat` is profiling the solution.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -59,9 +89,13 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(not(feature = "dhat-heap"))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            let input = $crate::template::Input::from_env().read(DAY);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
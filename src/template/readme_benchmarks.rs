@@ -2,7 +2,8 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
+use crate::template::alloc::{format_bytes, AllocStats};
+use crate::template::timings::{FailureKind, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -51,6 +52,27 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+/// Failed parts show their failure kind instead of a timing.
+pub fn format_part(timing: Option<String>, failure: Option<FailureKind>) -> String {
+    match failure {
+        Some(failure) => format!("✖ {}", failure.as_str()),
+        None => timing.unwrap_or_else(|| "-".into()),
+    }
+}
+
+/// Heap usage of a part as peak live bytes, total bytes and allocation count.
+fn format_heap(heap: Option<AllocStats>) -> String {
+    match heap {
+        Some(heap) => format!(
+            "{} / {} / {}",
+            format_bytes(heap.peak_bytes),
+            format_bytes(heap.bytes),
+            heap.allocations
+        ),
+        None => "-".into(),
+    }
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
@@ -58,21 +80,26 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Part 1 | Part 2 | Heap 1 | Heap 2 |".into(),
+        "| :---: | :---: | :---:  | :---: | :---: |".into(),
     ];
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            format_part(timing.part_1, timing.failures[0]),
+            format_part(timing.part_2, timing.failures[1]),
+            format_heap(timing.heap[0]),
+            format_heap(timing.heap[1])
         ));
     }
 
+    lines.push(String::new());
+    lines.push("Heap columns show peak / allocated bytes / allocation count.".into());
+
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
@@ -99,6 +126,8 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
+    use crate::template::alloc::AllocStats;
+    use crate::template::timings::FailureKind;
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
@@ -109,18 +138,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    failures: [None, None],
+                    heap: [None, None],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    failures: [None, None],
+                    heap: [None, None],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    failures: [None, None],
+                    heap: [None, None],
                 },
             ],
         }
@@ -166,11 +201,13 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Part 1 | Part 2 | Heap 1 | Heap 2 |",
+            "| :---: | :---: | :---:  | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `-` | `-` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | `-` | `-` |",
+            "",
+            "Heap columns show peak / allocated bytes / allocation count.",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +216,32 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_failed_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].failures = [None, Some(FailureKind::Panic)];
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40ms` | `✖ panic` | `-` | `-` |"));
+    }
+
+    #[test]
+    fn formats_heap_usage() {
+        let mut timings = get_mock_timings();
+        timings.data[0].heap = [
+            Some(AllocStats {
+                allocations: 12,
+                bytes: 3 * 1024 * 1024,
+                peak_bytes: 1536,
+            }),
+            None,
+        ];
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| `10ms` | `20ms` | `1.5 KiB / 3.0 MiB / 12` | `-` |"));
+    }
 }
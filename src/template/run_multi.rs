@@ -1,38 +1,75 @@
-use std::{collections::HashSet, io};
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    limits::Limits,
+    timings::{FailureKind, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Runs the given days, `jobs` at a time. Timed runs are always serial, so that benchmarks are
+/// not perturbed by each other.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    limits: &Limits,
+    jobs: usize,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
+    let mut killed: Vec<(Day, u8, FailureKind)> = vec![];
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
-
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut record = |day: Day, output: child_commands::SolutionOutput| {
+        if let Some((part, failure)) = output.killed {
+            println!("Part {part}: ✖ {}", failure.label());
+            killed.push((day, part, failure));
+
+            let mut val = child_commands::parse_exec_time(&output.lines, day);
+            val.failures[usize::from(part) - 1] = Some(failure);
+            timings.push(val);
+        } else if output.lines.is_empty() {
+            println!("Not solved.");
+        } else {
+            let val = child_commands::parse_exec_time(&output.lines, day);
+            timings.push(val);
+        }
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    if is_timed || jobs <= 1 {
+        for (i, day) in days.into_iter().enumerate() {
+            print_header(day, i > 0);
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, limits, true).unwrap();
+            record(day, output);
+        }
+    } else {
+        run_parallel(&days, is_release, limits, jobs, |i, day, output| {
+            print_header(day, i > 0);
+            for line in &output.lines {
+                println!("{line}");
+            }
+            for line in &output.stderr {
+                eprintln!("{line}");
             }
+            record(day, output);
         });
+    }
+
+    if !killed.is_empty() {
+        println!("\n{ANSI_BOLD}Killed:{ANSI_RESET}");
+        for (day, part, failure) in killed {
+            println!("Day {day} Part {part}: {}", failure.label());
+        }
+    }
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -46,6 +83,75 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+fn print_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Runs days on `jobs` worker threads with buffered output. Finished days are handed to
+/// `report` in day order, as soon as every day before them is done. Nothing runs if the solutions
+/// do not build.
+fn run_parallel(
+    days: &[Day],
+    is_release: bool,
+    limits: &Limits,
+    jobs: usize,
+    mut report: impl FnMut(usize, Day, child_commands::SolutionOutput),
+) {
+    // build every solution first, parallel `cargo run`s would otherwise wait on each other.
+    let mut args = vec!["build", "--quiet", "--bins"];
+    if is_release {
+        args.push("--release");
+    }
+    // cargo prints compile errors itself, running the days would only repeat them for each one.
+    match Command::new("cargo").args(&args).status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            eprintln!("Could not build solutions, not running them.");
+            return;
+        }
+        Err(e) => {
+            eprintln!("Could not build solutions: {e}");
+            return;
+        }
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                let output =
+                    child_commands::run_solution(day, false, is_release, limits, false).unwrap();
+                if sender.send((i, output)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_report = 0;
+        for (i, output) in receiver {
+            pending.insert(i, output);
+            while let Some(output) = pending.remove(&next_to_report) {
+                report(next_to_report, days[next_to_report], output);
+                next_to_report += 1;
+            }
+        }
+    });
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
@@ -67,74 +173,265 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::alloc::AllocStats;
+    use crate::template::limits::Limits;
+    use crate::template::timings::FailureKind;
     use crate::template::Day;
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        io::{self, BufRead, BufReader},
+        path::{Path, PathBuf},
+        process::{Command, ExitStatus, Stdio},
+        str::FromStr,
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::Instant,
     };
+    use tinyjson::JsonValue;
+
+    /// Output of a solution bin, and the part during which it was killed for exceeding a limit.
+    #[derive(Debug, Default)]
+    pub struct SolutionOutput {
+        pub lines: Vec<String>,
+        /// Lines of stderr, only kept when the output is not streamed.
+        pub stderr: Vec<String>,
+        pub killed: Option<(u8, FailureKind)>,
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day. With `stream` set, output is forwarded to
+    /// stdout/stderr while the solution runs, otherwise it is only buffered.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        limits: &Limits,
+        stream: bool,
+    ) -> Result<SolutionOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(SolutionOutput::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        // mirror `--time` flag to child invocations.
+        let bin_args: &[&str] = if is_timed { &["--time"] } else { &[] };
+
+        let mut command = if limits.is_limited(day) {
+            // build up front and run the bin itself, so that neither compiling nor cargo count
+            // towards the limits, and a timed out solution is killed rather than its `cargo run`.
+            let Some(executable) = build_solution(day, is_release)? else {
+                return Ok(SolutionOutput::default());
+            };
+            let mut command = Command::new(executable);
+            command.args(bin_args);
+            command
+        } else {
+            let day_padded = day.to_string();
+            let mut command = Command::new("cargo");
+            command.args(["run", "--quiet", "--bin", &day_padded]);
+            if is_release {
+                command.arg("--release");
+            }
+            command.arg("--").args(bin_args);
+            command
+        };
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        if let Some(bytes) = limits.memory_bytes(day) {
+            limit_memory(&mut command, bytes);
+        }
+
+        let mut cmd = command.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut output = SolutionOutput::default();
 
+        // stderr is forwarded as is, allocation failures are the only sign of a memory limit hit.
         let thread = thread::spawn(move || {
+            let mut is_out_of_memory = false;
+            let mut lines = vec![];
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                let line = line.unwrap();
+                is_out_of_memory |= line.starts_with("memory allocation of");
+                if stream {
+                    eprintln!("{line}");
+                } else {
+                    lines.push(line);
+                }
             });
+            (is_out_of_memory, lines)
+        });
+
+        // stdout is read on its own thread, so that the running part can be timed out.
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                if sender.send(line.unwrap()).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let mut part = 1;
+        let mut part_start = Instant::now();
+
+        loop {
+            let line = match limits.timeout(day, part) {
+                Some(timeout) => {
+                    let remaining = timeout.saturating_sub(part_start.elapsed());
+                    receiver.recv_timeout(remaining)
+                }
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match line {
+                Ok(line) => {
+                    if stream {
+                        println!("{line}");
+                    }
+                    // a part is done once its result line is complete, the next one starts.
+                    if let Some(done) = finished_part(&line) {
+                        part = done + 1;
+                        part_start = Instant::now();
+                    }
+                    output.lines.push(line);
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    cmd.kill()?;
+                    cmd.wait()?;
+                    output.killed = Some((part, FailureKind::Timeout));
+                    return Ok(output);
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let (is_out_of_memory, stderr) = thread.join().unwrap();
+        output.stderr = stderr;
+        let status = cmd.wait()?;
+
+        if is_out_of_memory || (limits.memory_bytes(day).is_some() && is_aborted(status)) {
+            output.killed = Some((part, FailureKind::OutOfMemory));
+        }
 
         Ok(output)
     }
 
+    /// Builds the bin of a day and returns the path of its executable, `None` if it does not
+    /// compile. Compile errors are printed by cargo, like `cargo run` would.
+    fn build_solution(day: Day, is_release: bool) -> Result<Option<PathBuf>, Error> {
+        let day_padded = day.to_string();
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--bin",
+            &day_padded,
+            "--message-format=json-render-diagnostics",
+        ];
+        if is_release {
+            args.push("--release");
+        }
+
+        let output = Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::inherit())
+            .output()?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        // the artifact message of the bin is the one with an executable.
+        let executable = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| {
+                let json = JsonValue::from_str(line).ok()?;
+                let json = json.get::<HashMap<String, JsonValue>>()?;
+                json.get("executable")?.get::<String>().map(PathBuf::from)
+            })
+            .ok_or_else(|| io::Error::other(format!("cargo built no executable for day {day}.")))?;
+
+        Ok(Some(executable))
+    }
+
+    /// Whether the solution was aborted, which is how Rust ends it when an allocation fails. This
+    /// also catches failed allocations whose `memory allocation of` message did not make it out.
+    #[cfg(unix)]
+    fn is_aborted(status: ExitStatus) -> bool {
+        use std::os::unix::process::ExitStatusExt;
+
+        status.signal() == Some(libc::SIGABRT)
+    }
+
+    #[cfg(not(unix))]
+    fn is_aborted(_status: ExitStatus) -> bool {
+        false
+    }
+
+    /// Limits the virtual memory (address space) of the solution. It counts reserved memory too,
+    /// e.g. the stacks of rayon's worker threads, not only the memory actually in use.
+    #[cfg(unix)]
+    fn limit_memory(command: &mut Command, bytes: u64) {
+        use std::os::unix::process::CommandExt;
+
+        let limit = libc::rlimit {
+            rlim_cur: bytes as libc::rlim_t,
+            rlim_max: bytes as libc::rlim_t,
+        };
+
+        // SAFETY: `setrlimit` is async-signal-safe, which is all that is allowed before `exec`.
+        unsafe {
+            command.pre_exec(move || {
+                if libc::setrlimit(libc::RLIMIT_AS, &limit) == 0 {
+                    Ok(())
+                } else {
+                    Err(io::Error::last_os_error())
+                }
+            });
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn limit_memory(_command: &mut Command, _bytes: u64) {
+        eprintln!("Memory limits are only supported on unix, running without one.");
+    }
+
+    /// Number of the part whose result is printed on a line of solution output.
+    fn finished_part(line: &str) -> Option<u8> {
+        let line = strip_ansi(line.rsplit('\r').next()?);
+        let (part, _) = line.strip_prefix("Part ")?.split_once(':')?;
+        part.parse().ok()
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            failures: [None, None],
+            heap: [None, None],
         };
 
+        for (part, answer) in parse_answers(&output.join("\n")) {
+            let index = match part.as_str() {
+                "Part 1" => 0,
+                "Part 2" => 1,
+                _ => continue,
+            };
+            timings.failures[index] = failure_kind(&answer);
+        }
+
+        // heap usage is printed after the timing of a part.
+        for line in output {
+            if let Some((part @ 1..=2, heap)) = finished_part(line).zip(AllocStats::parse(line)) {
+                timings.heap[usize::from(part - 1)] = Some(heap);
+            }
+        }
+
         output
             .iter()
             .filter_map(|l| {
@@ -163,6 +460,62 @@ pub mod child_commands {
         timings
     }
 
+    /// Extracts `(part, answer)` pairs from the output of a solution binary.
+    pub fn parse_answers(stdout: &str) -> Vec<(String, String)> {
+        let lines: Vec<&str> = stdout.lines().collect();
+        let mut answers = vec![];
+
+        for (i, line) in lines.iter().enumerate() {
+            // intermediate results are overwritten with a carriage return, keep the final one.
+            let line = strip_ansi(line.rsplit('\r').next().unwrap_or_default());
+            let Some((part, rest)) = line.split_once(": ") else {
+                continue;
+            };
+            if !part.starts_with("Part ") {
+                continue;
+            }
+
+            let answer = match rest.rsplit_once(" (") {
+                Some((answer, _)) => answer.trim(),
+                None => rest.trim(),
+            };
+            let answer = match answer {
+                // multiline answers are printed below their part.
+                "▼" => lines.get(i + 1).map(|next| strip_ansi(next)).unwrap_or_default(),
+                answer => answer.to_string(),
+            };
+
+            answers.push((part.to_string(), answer));
+        }
+
+        answers
+    }
+
+    fn strip_ansi(line: &str) -> String {
+        let mut stripped = String::with_capacity(line.len());
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // skip up to the final byte of the escape sequence, e.g. `m` in `\x1b[1m`.
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                stripped.push(c);
+            }
+        }
+        stripped
+    }
+
+    /// Failure kind of an answer printed as `✖ <kind>: <message>`, `None` for answers and
+    /// unsolved parts.
+    pub fn failure_kind(answer: &str) -> Option<FailureKind> {
+        let (kind, _) = answer.strip_prefix("✖ ")?.split_once(": ")?;
+        kind.parse().ok()
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -204,9 +557,32 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
         use crate::day;
+        use crate::template::alloc::AllocStats;
+        use crate::template::timings::FailureKind;
+
+        #[test]
+        fn parses_answers() {
+            let answers = parse_answers(
+                "Part 1: \x1b[1m142\x1b[0m\rPart 1: \x1b[1m142\x1b[0m (1.2ms)\n\
+                 Part 2: ✖             \n",
+            );
+            assert_eq!(
+                answers,
+                vec![
+                    ("Part 1".to_string(), "142".to_string()),
+                    ("Part 2".to_string(), "✖".to_string()),
+                ]
+            );
+        }
+
+        #[test]
+        fn parses_multiline_answers() {
+            let answers = parse_answers("Part 1: ▼  (2.0µs)\n#..#\n");
+            assert_eq!(answers, vec![("Part 1".to_string(), "#..#".to_string())]);
+        }
 
         #[test]
         fn parses_execution_times() {
@@ -238,6 +614,30 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_heap_usage() {
+            let output = [
+                "Part 1: 42 (1.5ms @ 100 samples) [heap: 3 allocs, 2048 bytes, peak 1024 bytes]"
+                    .into(),
+                "Part 2: 7 (1.0ms @ 100 samples)".into(),
+                "".into(),
+            ];
+            let res = parse_exec_time(&output, day!(1));
+            assert_eq!(res.part_1.unwrap(), "1.5ms");
+            assert_eq!(
+                res.heap,
+                [
+                    Some(AllocStats {
+                        allocations: 3,
+                        bytes: 2048,
+                        peak_bytes: 1024
+                    }),
+                    None
+                ]
+            );
+            assert_eq!(parse_answers(&output.join("\n"))[0].1, "42");
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -251,6 +651,24 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.failures, [None, None]);
+        }
+
+        #[test]
+        fn parses_failed_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖\rPart 1: ✖ panic: src/bin/01.rs:1:1: oops (1.0ms)".into(),
+                    "Part 2: ✖\rPart 2: ✖ error: panic: invalid input (2.0µs)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(
+                res.failures,
+                [Some(FailureKind::Panic), Some(FailureKind::Error)]
+            );
         }
     }
 }
@@ -1,23 +1,134 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::alloc::{self, AllocStats};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, Input, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+static PANIC_LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Set while [`catch_failures`] runs a part. Not thread-local, parts may panic on their own
+/// worker threads (e.g. rayon's), which pass the panic on to the part.
+static CATCHING: AtomicBool = AtomicBool::new(false);
+
+/// Why a part did not produce an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an `Err`, with its message.
+    Error(String),
+    /// The part panicked, with the panic message and location.
+    Panic(String),
+}
+
+/// Errors and panics are tagged with their kind, e.g. `panic: <message>`, so that
+/// `run_multi::child_commands::failure_kind` does not have to guess it from the message.
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Unsolved => write!(f, "no answer"),
+            Failure::Error(message) => write!(f, "error: {message}"),
+            Failure::Panic(message) => write!(f, "panic: {message}"),
+        }
+    }
+}
+
+/// Return types of solution parts: `Option<T>`, or `Result<T, E>` to say why a part failed.
+pub trait PartOutput {
+    type Answer: Display;
+
+    fn into_answer(self) -> Result<Self::Answer, Failure>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<T, Failure> {
+        self.ok_or(Failure::Unsolved)
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<T, Failure> {
+        self.map_err(|err| Failure::Error(err.to_string()))
+    }
+}
+
+/// Records the location of panics caught by [`catch_failures`] instead of printing them, they are
+/// printed as the result of their part. Other panics go to the previous hook.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.load(Ordering::SeqCst) {
+                let location = info.location().map(ToString::to_string);
+                *PANIC_LOCATION.lock().unwrap_or_else(|err| err.into_inner()) = location;
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown payload".into());
+
+    let location = PANIC_LOCATION
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .take();
+    match location {
+        Some(location) => format!("{location}: {message}"),
+        None => message,
+    }
+}
+
+/// Runs a part, turning panics into a [`Failure`].
+fn catch_failures<I, O: PartOutput>(func: impl Fn(I) -> O, input: I) -> Result<O::Answer, Failure> {
+    CATCHING.store(true, Ordering::SeqCst);
+    let output = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+    CATCHING.store(false, Ordering::SeqCst);
+
+    output
+        .map_err(|payload| Failure::Panic(panic_message(payload.as_ref())))
+        .and_then(PartOutput::into_answer)
+}
+
+pub fn run_part<I: Clone, O: PartOutput>(func: impl Fn(I) -> O, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
+    install_panic_hook();
+
+    let func = |input| catch_failures(&func, input);
+    let (result, duration, samples, heap) = run_timed(func, input, day, part, |result| {
+        print_result(result, &part_str, "")
+    });
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let mut duration_str = format_duration(&duration, samples);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Some(heap) = heap {
+        duration_str.push_str(&format!(" {heap}"));
+    }
+
+    print_result(&result, &part_str, &duration_str);
 
-    if let Some(result) = result {
+    if let Ok(result) = result {
         submit_result(result, day, part);
     }
 }
@@ -25,31 +136,55 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Failed parts are not benched. With `--profile-cpu`, the benchmark is profiled. Heap usage is
+/// counted during the first execution only, and not at all while `dhat` replaces the counting
+/// allocator.
 fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+    func: impl Fn(I) -> Result<T, Failure>,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    day: Day,
+    part: u8,
+    hook: impl Fn(&Result<T, Failure>),
+) -> (Result<T, Failure>, Duration, u128, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, heap) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
+    let heap = (!cfg!(feature = "dhat-heap")).then_some(heap);
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let run = if result.is_ok() && std::env::args().any(|x| x == "--time") {
+        let bench = || bench(func, input, &base_time);
+        if env::args().any(|x| x == "--profile-cpu") {
+            profile_cpu(day, part, bench)
+        } else {
+            bench()
+        }
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, heap)
+}
+
+/// Samples the call stacks of `func` into `data/profiles/<day>-part<N>.{folded,svg}`.
+#[cfg(all(feature = "profile-cpu", unix))]
+fn profile_cpu<T>(day: Day, part: u8, func: impl FnOnce() -> T) -> T {
+    crate::template::cpu_profile::profile(day, part, func)
+}
+
+#[cfg(not(all(feature = "profile-cpu", unix)))]
+fn profile_cpu<T>(_day: Day, _part: u8, func: impl FnOnce() -> T) -> T {
+    eprintln!("CPU profiling needs the `profile-cpu` feature on unix, running without it.");
+    func()
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -99,11 +234,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Result<T, Failure>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -123,7 +258,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Err(Failure::Unsolved) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -131,6 +266,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(failure) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {failure}{duration_str}");
+            }
+        }
     }
 }
 
@@ -164,6 +307,11 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if Input::from_env() != Input::Puzzle {
+        eprintln!("Not submitting, the result was not computed from the puzzle input.");
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -1,10 +1,53 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::alloc::AllocStats;
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Why a part has no timing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureKind {
+    /// The part returned an `Err`.
+    Error,
+    Panic,
+    /// The part was killed for running longer than its timeout.
+    Timeout,
+    /// The part was killed for exceeding its memory limit.
+    OutOfMemory,
+}
+
+impl FailureKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FailureKind::Error => "error",
+            FailureKind::Panic => "panic",
+            FailureKind::Timeout => "timeout",
+            FailureKind::OutOfMemory => "oom",
+        }
+    }
+
+    /// Label of the failure in command-line summaries, e.g. `TIMEOUT`.
+    pub fn label(self) -> String {
+        self.as_str().to_uppercase()
+    }
+}
+
+impl FromStr for FailureKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(FailureKind::Error),
+            "panic" => Ok(FailureKind::Panic),
+            "timeout" => Ok(FailureKind::Timeout),
+            "oom" => Ok(FailureKind::OutOfMemory),
+            _ => Err(format!("unknown failure kind `{s}`.")),
+        }
+    }
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
@@ -12,6 +55,10 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Failures of part 1 and part 2, if any.
+    pub failures: [Option<FailureKind>; 2],
+    /// Heap usage of part 1 and part 2, if it was counted.
+    pub heap: [Option<AllocStats>; 2],
 }
 
 /// Represents benchmark times for a set of days.
@@ -140,10 +187,64 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (i, failure) in value.failures.iter().enumerate() {
+            if let Some(failure) = failure {
+                map.insert(
+                    format!("failure_{}", i + 1),
+                    JsonValue::String(failure.as_str().into()),
+                );
+            }
+        }
+
+        for (i, heap) in value.heap.iter().enumerate() {
+            if let Some(heap) = heap {
+                map.insert(format!("heap_{}", i + 1), JsonValue::from(heap));
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
         JsonValue::Object(map)
     }
 }
 
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|n| n as u64)
+                .ok_or(format!("Expected heap stats {key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: number("allocations")?,
+            bytes: number("bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -173,11 +274,27 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // failures are only stored for failed parts.
+        let failure = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    v.get::<String>()
+                        .and_then(|kind| FailureKind::from_str(kind).ok())
+                        .ok_or(format!("Expected timing.{key} to be a failure kind."))
+                })
+                .transpose()
+        };
+
+        // heap usage is missing for timings stored before it was counted.
+        let heap = |key: &str| json.get(key).map(AllocStats::try_from).transpose();
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            failures: [failure("failure_1")?, failure("failure_2")?],
+            heap: [heap("heap_1")?, heap("heap_2")?],
         })
     }
 }
@@ -198,25 +315,34 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    failures: [None, None],
+                    heap: [None, None],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    failures: [None, None],
+                    heap: [None, None],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    failures: [None, None],
+                    heap: [None, None],
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{FailureKind, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -230,6 +356,17 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_failed_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": "1ms", "failure_1": "panic", "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.failures, [Some(FailureKind::Panic), None]);
+
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "failure_1": "oops", "total_nanos": 0 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -254,9 +391,20 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{FailureKind, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn serializes_failures() {
+            let mut timings = get_mock_timings();
+            timings.data[2].failures = [None, Some(FailureKind::Error)];
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[2].failures, [None, Some(FailureKind::Error)]);
+            assert_eq!(parsed.data[0].failures, [None, None]);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -289,6 +437,8 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    failures: [None, None],
+                    heap: [None, None],
                 }],
             };
 
@@ -303,6 +453,8 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    failures: [None, None],
+                    heap: [None, None],
                 }],
             };
 
@@ -317,6 +469,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    failures: [None, None],
+                    heap: [None, None],
                 }],
             };
 
@@ -341,6 +495,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failures: [None, None],
+                    heap: [None, None],
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +517,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failures: [None, None],
+                    heap: [None, None],
                 }],
             };
             let merged = timings.merge(&other);
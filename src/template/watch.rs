@@ -0,0 +1,169 @@
+/// Live re-run mode of `cargo solve DD --watch`.
+///
+/// Sources and inputs of a day are polled for modification times, which needs no platform
+/// specific file system notifications. Bursts of changes are debounced into a single re-run of
+/// the solution and its example tests, and answers are shown next to those of the previous run.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{Duration, SystemTime};
+use std::{fs, io, thread};
+
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+use super::run_multi::{child_commands::parse_answers, get_path_for_bin};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Changes less than this apart are grouped, editors and formatters often write files in bursts.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+fn watched_paths(day: Day) -> Vec<PathBuf> {
+    vec![
+        get_path_for_bin(day).into(),
+        "./src/custom_grid".into(),
+        format!("./data/inputs/{day}.txt").into(),
+        format!("./data/examples/{day}.txt").into(),
+        format!("./data/examples/{day}-1.txt").into(),
+        format!("./data/examples/{day}-2.txt").into(),
+    ]
+}
+
+/// Modification times of every watched file, directories being walked recursively. Missing
+/// paths are skipped, so creating one of them is seen as a change.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    fn visit(path: &Path, snapshot: &mut Snapshot) -> io::Result<()> {
+        let metadata = fs::metadata(path)?;
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path)? {
+                let _ = visit(&entry?.path(), snapshot);
+            }
+        } else {
+            snapshot.insert(path.to_path_buf(), metadata.modified()?);
+        }
+        Ok(())
+    }
+
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        let _ = visit(path, &mut snapshot);
+    }
+    snapshot
+}
+
+/// Blocks until the watched files change, then until they stop changing.
+fn wait_for_changes(paths: &[PathBuf], last: &Snapshot) -> Snapshot {
+    let mut current = snapshot(paths);
+    while &current == last {
+        thread::sleep(POLL_INTERVAL);
+        current = snapshot(paths);
+    }
+
+    loop {
+        thread::sleep(DEBOUNCE);
+        let next = snapshot(paths);
+        if next == current {
+            return current;
+        }
+        current = next;
+    }
+}
+
+fn cargo(args: &[&str]) -> io::Result<Output> {
+    Command::new("cargo").args(args).output()
+}
+
+fn print_answers(answers: &[(String, String)], previous: &[(String, String)]) {
+    let width = answers
+        .iter()
+        .chain(previous)
+        .map(|(_, answer)| answer.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("previous".len());
+
+    println!("        {ANSI_ITALIC}{:<width$}  current{ANSI_RESET}", "previous");
+    for (part, answer) in answers {
+        let before = previous
+            .iter()
+            .find(|(previous_part, _)| previous_part == part)
+            .map_or("-", |(_, answer)| answer.as_str());
+        let marker = if before == answer { "" } else { "  ✱" };
+        println!("{part}  {before:<width$}  {ANSI_BOLD}{answer}{ANSI_RESET}{marker}");
+    }
+}
+
+/// Runs the solution and the example tests once, returning the answers of this run.
+fn run(day: Day, release: bool, previous: Vec<(String, String)>) -> Vec<(String, String)> {
+    let day_padded = day.to_string();
+    let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    if release {
+        args.push("--release");
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    let answers = match cargo(&args) {
+        Ok(output) if output.status.success() => {
+            let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+            print_answers(&answers, &previous);
+            answers
+        }
+        Ok(output) => {
+            eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+            previous
+        }
+        Err(err) => {
+            eprintln!("Could not run cargo: {err}");
+            previous
+        }
+    };
+
+    match cargo(&["test", "--quiet", "--bin", &day_padded]) {
+        Ok(output) if output.status.success() => println!("Example tests: passed"),
+        Ok(output) => {
+            println!("Example tests: {ANSI_BOLD}failed{ANSI_RESET}");
+            println!("{}", String::from_utf8_lossy(&output.stdout).trim());
+            eprintln!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Err(err) => eprintln!("Could not run cargo: {err}"),
+    }
+
+    answers
+}
+
+pub fn watch(day: Day, release: bool) {
+    let paths = watched_paths(day);
+    let mut last = snapshot(&paths);
+    let mut answers = run(day, release, vec![]);
+
+    loop {
+        println!("\n{ANSI_ITALIC}Watching day {day} for changes, press Ctrl-C to stop.{ANSI_RESET}");
+        last = wait_for_changes(&paths, &last);
+        println!();
+        answers = run(day, release, answers);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_changes_in_directories() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        let paths = vec![dir.clone(), dir.join("missing.txt")];
+
+        let before = snapshot(&paths);
+        fs::write(dir.join("nested").join("file.txt"), "1").unwrap();
+        let after = snapshot(&paths);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(before.is_empty());
+        assert_eq!(after.len(), 1);
+    }
+}